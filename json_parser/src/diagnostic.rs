@@ -0,0 +1,326 @@
+use std::{error::Error, fmt};
+
+use crate::{JsonType, ParserErr, ParserErrKind, TokenKind};
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            TokenKind::LCurlyBracket => "`{`",
+            TokenKind::RCurlyBracket => "`}`",
+            TokenKind::LBracket => "`[`",
+            TokenKind::RBracket => "`]`",
+            TokenKind::Colon => "`:`",
+            TokenKind::Comma => "`,`",
+            TokenKind::String(_) => "a string",
+            TokenKind::Number => "a number",
+            TokenKind::Bool => "`true` or `false`",
+            TokenKind::Null => "`null`",
+        };
+
+        f.write_str(description)
+    }
+}
+
+impl fmt::Display for JsonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            JsonType::Object => "object",
+            JsonType::Array => "array",
+            JsonType::String => "string",
+            JsonType::Number => "number",
+            JsonType::Bool => "boolean",
+            JsonType::Null => "null",
+        };
+
+        f.write_str(name)
+    }
+}
+
+impl fmt::Display for ParserErrKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParserErrKind::UnrecognisedSymbol => write!(f, "unrecognised symbol"),
+            ParserErrKind::UnrecognisedLiteral => {
+                write!(
+                    f,
+                    "unrecognised literal, expected `true`, `false` or `null`"
+                )
+            }
+            ParserErrKind::InvalidEscapeSequence => write!(f, "invalid escape sequence"),
+            ParserErrKind::LoneSurrogate => {
+                write!(
+                    f,
+                    "unicode escape is half of a surrogate pair, but the other half is missing"
+                )
+            }
+            ParserErrKind::ControlCharacterInString(chr) => {
+                write!(
+                    f,
+                    "control character U+{:04X} must be escaped inside a string",
+                    *chr as u32
+                )?;
+
+                // A raw newline most likely means the closing quote was forgotten
+                if *chr == '\n' {
+                    write!(f, " (is the closing quote missing?)")?;
+                }
+                Ok(())
+            }
+            ParserErrKind::LeadingZero => write!(f, "numbers cannot have leading zeros"),
+            ParserErrKind::MissingIntegerDigits => write!(f, "expected a digit after `-`"),
+            ParserErrKind::MissingFractionDigits => {
+                write!(f, "expected a digit after the decimal point")
+            }
+            ParserErrKind::MissingExponentDigits => write!(f, "expected a digit in the exponent"),
+            ParserErrKind::UnexpectedCharacterInNumber => {
+                write!(f, "unexpected character at the end of a number")
+            }
+            ParserErrKind::ExpectedEndOfSource => {
+                write!(f, "expected the end of the JSON, but found more data")
+            }
+            ParserErrKind::ExpectedToken(kind) => write!(f, "expected {kind}"),
+            ParserErrKind::UnexpectedToken => write!(f, "unexpected token"),
+            ParserErrKind::InvalidNumber => {
+                write!(f, "number cannot be represented by the expected type")
+            }
+            ParserErrKind::NumberOutOfRange(expected) => {
+                write!(f, "number is out of range for `{expected}`")
+            }
+            ParserErrKind::InvalidType { expected, found } => {
+                write!(f, "invalid type, expected {expected} but found {found}")
+            }
+            ParserErrKind::UnknownProperty => write!(f, "unknown property"),
+            ParserErrKind::MissingProperties(names) => {
+                let names: Vec<_> = names.iter().map(|name| format!("`{name}`")).collect();
+                match names.as_slice() {
+                    [name] => write!(f, "missing property {name}"),
+                    _ => write!(f, "missing properties {}", names.join(", ")),
+                }
+            }
+            ParserErrKind::DuplicateProperty { key, first } => write!(
+                f,
+                "duplicate property `{key}`, first defined at line {}, column {}",
+                first.start.line, first.start.column
+            ),
+            ParserErrKind::UnknownVariant { found, expected } => {
+                write!(f, "unknown variant `{found}`, ")?;
+                match expected {
+                    [] => write!(f, "there are no variants"),
+                    [only] => write!(f, "expected `{only}`"),
+                    _ => {
+                        let expected: Vec<_> = expected.iter().map(|x| format!("`{x}`")).collect();
+                        write!(f, "expected one of {}", expected.join(", "))
+                    }
+                }
+            }
+            ParserErrKind::NoMatchingVariant(expected) => {
+                write!(f, "value doesn't match any variant of `{expected}`")
+            }
+            ParserErrKind::InvalidLength { expected, found } => {
+                write!(f, "invalid length, expected {expected} but found {found}")
+            }
+            ParserErrKind::DepthLimitExceeded => {
+                write!(f, "arrays and objects are nested too deeply")
+            }
+            ParserErrKind::UnexpectedEndOfSource => write!(f, "unexpected end of JSON"),
+        }
+    }
+}
+
+impl fmt::Display for ParserErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at ", self.kind)?;
+
+        // The root path doesn't add anything, so only show it for nested values
+        if !self.path.segments().is_empty() {
+            write!(f, "{}, ", self.path)?;
+        }
+
+        write!(
+            f,
+            "line {}, column {}",
+            self.span.start.line, self.span.start.column
+        )
+    }
+}
+
+impl Error for ParserErr {}
+
+impl ParserErr {
+    // Render the error like rustc does, showing the line of `source` the error is on with the
+    // lexeme underlined. `source` must be the same string that was parsed
+    pub fn render(&self, source: &str) -> String {
+        let start = self.span.start;
+        let line = source.lines().nth(start.line - 1).unwrap_or("");
+
+        // Keep any tabs in the padding, so the caret lines up no matter how wide they are shown
+        let padding: String = line
+            .chars()
+            .take(start.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        // Only underline up to the end of the line if the lexeme continues onto the next one
+        let line_len = line.chars().count();
+        let end_column = if self.span.end.line == start.line {
+            self.span.end.column
+        } else {
+            line_len + 1
+        };
+        let underline = "^".repeat(end_column.saturating_sub(start.column).max(1));
+
+        let line_number = start.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        let mut rendered = format!(
+            "error: {}\n{gutter}--> line {}, column {}\n{gutter} |\n{line_number} | {line}\n{gutter} | {padding}{underline}\n",
+            self.kind, start.line, start.column
+        );
+
+        if !self.path.segments().is_empty() {
+            rendered.push_str(&format!("{gutter} = path: {}\n", self.path));
+        }
+
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Parser, Span, json_value::JsonValue};
+
+    use super::*;
+
+    fn parse_err(source: &str) -> ParserErr {
+        Parser::parse::<JsonValue>(source).unwrap_err()
+    }
+
+    #[test]
+    fn test_display() {
+        let err = parse_err("{\n  \"key\" 5\n}");
+        assert_eq!("expected `:` at line 2, column 9", err.to_string());
+    }
+
+    #[test]
+    fn test_display_path() {
+        let err = parse_err(r#"{"history": [{}, {"success": tru}]}"#);
+        assert_eq!(
+            "unrecognised literal, expected `true`, `false` or `null` at $.history[1].success, line 1, column 30",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_render_path() {
+        let source = "[\n  [1, 2],\n  [3, 0123]\n]";
+        assert_eq!(
+            "error: numbers cannot have leading zeros\n --> line 3, column 7\n  |\n3 |   [3, 0123]\n  |       ^^^^\n  = path: $[1][1]\n",
+            parse_err(source).render(source)
+        );
+    }
+
+    #[test]
+    fn test_error_trait() {
+        let err: Box<dyn Error> = Box::new(parse_err("[1, 2"));
+        assert_eq!(
+            "unexpected end of JSON at line 1, column 5",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let source = "{\n  \"name\": \"Jane\",\n  \"age\" 32\n}";
+        assert_eq!(
+            "error: expected `:`\n --> line 3, column 9\n  |\n3 |   \"age\" 32\n  |         ^^\n",
+            parse_err(source).render(source)
+        );
+    }
+
+    #[test]
+    fn test_render_tabs() {
+        let source = "{\n\t\"a\": tru\n}";
+        assert_eq!(
+            "error: unrecognised literal, expected `true`, `false` or `null`\n --> line 2, column 7\n  |\n2 | \t\"a\": tru\n  | \t     ^^^\n  = path: $.a\n",
+            parse_err(source).render(source)
+        );
+    }
+
+    #[test]
+    fn test_render_multi_line_lexeme() {
+        let source = "[\n\n\n\n\n\n\n\n\n\"unterminated\nstring\"]";
+        assert_eq!(
+            "error: control character U+000A must be escaped inside a string (is the closing quote missing?)\n  --> line 10, column 1\n   |\n10 | \"unterminated\n   | ^^^^^^^^^^^^^\n   = path: $[0]\n",
+            parse_err(source).render(source)
+        );
+    }
+
+    #[test]
+    fn test_messages() {
+        let cases = vec![
+            (
+                ParserErrKind::ControlCharacterInString('\t'),
+                "control character U+0009 must be escaped inside a string",
+            ),
+            (
+                ParserErrKind::ControlCharacterInString('\n'),
+                "control character U+000A must be escaped inside a string (is the closing quote missing?)",
+            ),
+            (
+                ParserErrKind::ExpectedToken(TokenKind::RBracket),
+                "expected `]`",
+            ),
+            (
+                ParserErrKind::InvalidType {
+                    expected: "u32",
+                    found: JsonType::String,
+                },
+                "invalid type, expected u32 but found string",
+            ),
+            (
+                ParserErrKind::NumberOutOfRange("u8"),
+                "number is out of range for `u8`",
+            ),
+            (
+                ParserErrKind::MissingProperties(vec!["age".to_string()]),
+                "missing property `age`",
+            ),
+            (
+                ParserErrKind::MissingProperties(vec!["age".to_string(), "name".to_string()]),
+                "missing properties `age`, `name`",
+            ),
+            (
+                ParserErrKind::DuplicateProperty {
+                    key: "age".to_string(),
+                    first: Box::new(Span::single_line(1, 6)),
+                },
+                "duplicate property `age`, first defined at line 1, column 2",
+            ),
+            (
+                ParserErrKind::UnknownVariant {
+                    found: "blue".to_string(),
+                    expected: &["dark", "light"],
+                },
+                "unknown variant `blue`, expected one of `dark`, `light`",
+            ),
+            (
+                ParserErrKind::UnknownVariant {
+                    found: "blue".to_string(),
+                    expected: &["dark"],
+                },
+                "unknown variant `blue`, expected `dark`",
+            ),
+            (
+                ParserErrKind::InvalidLength {
+                    expected: 2,
+                    found: 3,
+                },
+                "invalid length, expected 2 but found 3",
+            ),
+        ];
+
+        for (kind, expected) in cases {
+            assert_eq!(expected, kind.to_string());
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    Serialiser,
+    pretty::{PrettyOptions, PrettyPrinter},
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum JsonValue {
@@ -10,3 +15,78 @@ pub enum JsonValue {
     Bool(bool),
     Null,
 }
+
+impl JsonValue {
+    // Both of these panic if a number is NaN or infinite, as JSON cannot represent them
+    pub fn to_string_compact(&self) -> String {
+        Serialiser::serialise(self)
+    }
+
+    pub fn to_string_pretty(&self, options: &PrettyOptions) -> String {
+        PrettyPrinter::print(self, options)
+    }
+}
+
+// Compact by default, or pretty printed with the default options when using `{:#}`
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(&self.to_string_pretty(&PrettyOptions::default()))
+        } else {
+            f.write_str(&self.to_string_compact())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_compact() {
+        let value = JsonValue::Array(vec![
+            JsonValue::Number(1.5),
+            JsonValue::String("a\"b".to_string()),
+            JsonValue::Null,
+        ]);
+        assert_eq!(r#"[1.5,"a\"b",null]"#, value.to_string_compact());
+    }
+
+    #[test]
+    fn test_display() {
+        let value = JsonValue::Array(vec![JsonValue::Bool(true)]);
+        assert_eq!("[true]", format!("{value}"));
+        assert_eq!("[\n  true\n]", format!("{value:#}"));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let source = r#"{
+            "name": "Jane \"JD\" Doe",
+            "age": 32,
+            "balance": 10457.89,
+            "scientific": -1.2e-7,
+            "big": 1e300,
+            "nickname": null,
+            "unicode": "©😀\u0001",
+            "tags": ["user", [], {}],
+            "nested": {"deep": {"deeper": [true, false]}}
+        }"#;
+        let value = Parser::parse::<JsonValue>(source).unwrap();
+
+        assert_eq!(Ok(value.clone()), Parser::parse(&format!("{value}")));
+        assert_eq!(Ok(value.clone()), Parser::parse(&format!("{value:#}")));
+
+        let options = PrettyOptions {
+            sort_keys: true,
+            array_line_width: Some(40),
+            ..Default::default()
+        };
+        assert_eq!(
+            Ok(value.clone()),
+            Parser::parse(&value.to_string_pretty(&options))
+        );
+    }
+}
@@ -1,8 +1,21 @@
+mod diagnostic;
 pub mod json_value;
 pub mod parse_impl;
+pub mod parse_options;
 pub mod parser;
+pub mod path;
+pub mod pretty;
 mod scanner;
+pub mod serialise_impl;
+pub mod serialiser;
 mod token;
 
-pub use parser::{Parse, Parser, ParserErr, ParserErrKind};
-pub use token::TokenKind;
+pub use parse_options::{DuplicateKeys, ParseOptions, UnknownFields};
+pub use parser::{Checkpoint, JsonType, Parse, ParseFields, Parser, ParserErr, ParserErrKind};
+pub use path::{JsonPath, PathSegment};
+pub use pretty::{Indent, PrettyOptions};
+pub use serialiser::{Serialise, SerialiseErr, SerialiseFields, Serialiser};
+pub use token::{Position, Span, Token, TokenKind};
+
+#[cfg(feature = "derive")]
+pub use json_parser_macros::{JsonDeserialise, JsonSerialise};
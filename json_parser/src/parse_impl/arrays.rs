@@ -1,15 +1,22 @@
-use crate::{Parse, Parser, ParserErr, ParserErrKind, token::TokenKind};
+use crate::{Parse, Parser, ParserErr, ParserErrKind, PathSegment, token::TokenKind};
 
 impl<T: Parse> Parse for Vec<T> {
     fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
-        parser.consume(TokenKind::LBracket)?;
+        let token = parser.advance()?;
+        if token.kind != TokenKind::LBracket {
+            return Err(parser.make_type_err("array", &token));
+        }
+        parser.enter_nested()?;
 
         let mut elems = Vec::new();
         let mut had_comma = false;
 
         // Loop through all elements, until reaching closing bracket
         while !parser.check(TokenKind::RBracket)? {
+            parser.push_path(PathSegment::Index(elems.len()));
             let elem = T::parse(parser)?;
+            parser.pop_path();
+
             elems.push(elem);
 
             // Once no comma at end, we have reached end of array
@@ -21,12 +28,13 @@ impl<T: Parse> Parse for Vec<T> {
             }
         }
 
-        // No trailing comma
-        if had_comma {
+        // No trailing comma, unless the options allow it
+        if had_comma && !parser.options().allow_trailing_commas {
             return Err(parser.make_err_prev(ParserErrKind::UnexpectedToken));
         }
 
         parser.consume(TokenKind::RBracket)?;
+        parser.exit_nested();
 
         Ok(elems)
     }
@@ -34,7 +42,7 @@ impl<T: Parse> Parse for Vec<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::json_value::JsonValue;
+    use crate::{JsonPath, JsonType, Span, json_value::JsonValue};
 
     use super::*;
 
@@ -68,11 +76,25 @@ mod tests {
                 kind: ParserErrKind::UnexpectedToken,
                 line: 1,
                 lexeme: ",".to_string(),
+                span: Box::new(Span::single_line(5, 6)),
+                path: JsonPath::default(),
             }),
             result
         );
     }
 
+    #[test]
+    fn test_invalid_type() {
+        let result = Parser::parse::<Vec<u32>>(r#"{"a": 1}"#);
+        assert_eq!(
+            Err(ParserErrKind::InvalidType {
+                expected: "array",
+                found: JsonType::Object,
+            }),
+            result.map_err(|x| x.kind)
+        );
+    }
+
     #[test]
     fn test_mixed() {
         let expected_elems = vec![
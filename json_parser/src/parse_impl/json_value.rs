@@ -23,6 +23,8 @@ impl Parse for JsonValue {
 
 #[cfg(test)]
 mod tests {
+    use crate::{DuplicateKeys, JsonPath, ParseOptions, PathSegment, Span};
+
     use super::*;
 
     #[test]
@@ -82,8 +84,50 @@ mod tests {
                 kind: ParserErrKind::UnexpectedToken,
                 line: 1,
                 lexeme: ":".to_string(),
+                span: Box::new(Span::single_line(0, 1)),
+                path: JsonPath::default(),
             }),
             result
         );
     }
+
+    #[test]
+    fn test_duplicate_keys() {
+        let source = r#"[{"id": 1, "id": "two"}]"#;
+        let parse = |duplicate_keys| {
+            let options = ParseOptions::new().duplicate_keys(duplicate_keys);
+            Parser::parse_with::<JsonValue>(source, &options)
+        };
+        let object = |value| {
+            JsonValue::Array(vec![JsonValue::Object(HashMap::from([(
+                "id".to_string(),
+                value,
+            )]))])
+        };
+
+        assert_eq!(
+            Ok(object(JsonValue::String("two".to_string()))),
+            parse(DuplicateKeys::LastWins)
+        );
+        assert_eq!(
+            Ok(object(JsonValue::Number(1.0))),
+            parse(DuplicateKeys::FirstWins)
+        );
+        assert_eq!(
+            Err(ParserErr {
+                kind: ParserErrKind::DuplicateProperty {
+                    key: "id".to_string(),
+                    first: Box::new(Span::single_line(2, 6)),
+                },
+                line: 1,
+                lexeme: r#""id""#.to_string(),
+                span: Box::new(Span::single_line(11, 15)),
+                path: JsonPath(vec![
+                    PathSegment::Index(0),
+                    PathSegment::Key("id".to_string())
+                ]),
+            }),
+            parse(DuplicateKeys::Error)
+        );
+    }
 }
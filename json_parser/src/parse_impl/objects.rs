@@ -1,10 +1,15 @@
 use std::collections::HashMap;
 
-use crate::{Parse, Parser, ParserErr, ParserErrKind, token::TokenKind};
+use crate::{Parse, Parser, ParserErr, ParserErrKind, PathSegment, token::TokenKind};
 
 impl<T: Parse> Parse for HashMap<String, T> {
     fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
-        parser.consume(TokenKind::LCurlyBracket)?;
+        let token = parser.advance()?;
+        if token.kind != TokenKind::LCurlyBracket {
+            return Err(parser.make_type_err("object", &token));
+        }
+        parser.enter_nested()?;
+        parser.start_object();
 
         let mut props = HashMap::new();
         let mut had_comma = false;
@@ -12,12 +17,18 @@ impl<T: Parse> Parse for HashMap<String, T> {
         // Loop through all properties, until reaching closing bracket
         while !parser.check(TokenKind::RCurlyBracket)? {
             let token = parser.advance()?;
-            match token.kind {
+            match &token.kind {
                 TokenKind::String(key) => {
                     parser.consume(TokenKind::Colon)?;
 
-                    let value = T::parse(parser)?;
-                    props.insert(key, value);
+                    parser.push_path(PathSegment::Key(key.clone()));
+                    if parser.check_key(&token)? {
+                        let value = T::parse(parser)?;
+                        props.insert(key.clone(), value);
+                    } else {
+                        parser.skip_value()?;
+                    }
+                    parser.pop_path();
 
                     // Once no comma at end, we have reached end of object
                     had_comma = parser.check(TokenKind::Comma)?;
@@ -31,12 +42,14 @@ impl<T: Parse> Parse for HashMap<String, T> {
             }
         }
 
-        // No trailing comma
-        if had_comma {
+        // No trailing comma, unless the options allow it
+        if had_comma && !parser.options().allow_trailing_commas {
             return Err(parser.make_err_prev(ParserErrKind::UnexpectedToken));
         }
 
         parser.consume(TokenKind::RCurlyBracket)?;
+        parser.end_object();
+        parser.exit_nested();
 
         Ok(props)
     }
@@ -44,7 +57,9 @@ impl<T: Parse> Parse for HashMap<String, T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::json_value::JsonValue;
+    use crate::{
+        DuplicateKeys, JsonPath, JsonType, ParseOptions, Position, Span, json_value::JsonValue,
+    };
 
     use super::*;
 
@@ -91,6 +106,8 @@ mod tests {
                 kind: ParserErrKind::UnrecognisedLiteral,
                 line: 1,
                 lexeme: "prop".to_string(),
+                span: Box::new(Span::single_line(1, 5)),
+                path: JsonPath::default(),
             }),
             result
         );
@@ -104,6 +121,8 @@ mod tests {
                 kind: ParserErrKind::UnexpectedToken,
                 line: 1,
                 lexeme: "true".to_string(),
+                span: Box::new(Span::single_line(1, 5)),
+                path: JsonPath::default(),
             }),
             result
         );
@@ -117,6 +136,8 @@ mod tests {
                 kind: ParserErrKind::UnexpectedToken,
                 line: 1,
                 lexeme: ",".to_string(),
+                span: Box::new(Span::single_line(26, 27)),
+                path: JsonPath::default(),
             }),
             result
         );
@@ -130,11 +151,25 @@ mod tests {
                 kind: ParserErrKind::ExpectedToken(TokenKind::Colon),
                 line: 1,
                 lexeme: "5".to_string(),
+                span: Box::new(Span::single_line(8, 9)),
+                path: JsonPath::default(),
             }),
             result
         );
     }
 
+    #[test]
+    fn test_invalid_type() {
+        let result = Parser::parse::<HashMap<String, u32>>("[1, 2]");
+        assert_eq!(
+            Err(ParserErrKind::InvalidType {
+                expected: "object",
+                found: JsonType::Array,
+            }),
+            result.map_err(|x| x.kind)
+        );
+    }
+
     #[test]
     fn test_nested() {
         let expected_props = HashMap::from([
@@ -159,4 +194,82 @@ mod tests {
         );
         assert_eq!(Ok(expected_props), result);
     }
+
+    #[test]
+    fn test_duplicate_keys() {
+        let source = r#"{"a": 1, "b": 2, "a": 3}"#;
+        let parse = |duplicate_keys| {
+            let options = ParseOptions::new().duplicate_keys(duplicate_keys);
+            Parser::parse_with::<HashMap<String, u32>>(source, &options)
+        };
+
+        let expected = HashMap::from([("a".to_string(), 3), ("b".to_string(), 2)]);
+        assert_eq!(Ok(expected), parse(DuplicateKeys::LastWins));
+
+        let expected = HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+        assert_eq!(Ok(expected), parse(DuplicateKeys::FirstWins));
+
+        assert_eq!(
+            Err(ParserErr {
+                kind: ParserErrKind::DuplicateProperty {
+                    key: "a".to_string(),
+                    first: Box::new(Span::single_line(1, 4)),
+                },
+                line: 1,
+                lexeme: r#""a""#.to_string(),
+                span: Box::new(Span::single_line(17, 20)),
+                path: JsonPath(vec![PathSegment::Key("a".to_string())]),
+            }),
+            parse(DuplicateKeys::Error)
+        );
+    }
+
+    #[test]
+    fn test_duplicate_keys_skipped_value() {
+        // The later value isn't parsed at all, so doesn't need to be the right type
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeys::FirstWins);
+        let result = Parser::parse_with::<HashMap<String, u32>>(
+            r#"{"a": 1, "a": {"nested": [true]}}"#,
+            &options,
+        );
+        assert_eq!(Ok(HashMap::from([("a".to_string(), 1)])), result);
+    }
+
+    #[test]
+    fn test_duplicate_keys_per_object() {
+        // Keys are only compared within the same object, not with nested or sibling objects
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeys::Error);
+        let result = Parser::parse_with::<HashMap<String, HashMap<String, u32>>>(
+            r#"{"a": {"a": 1}, "b": {"a": 2}}"#,
+            &options,
+        );
+        assert!(result.is_ok());
+
+        let result = Parser::parse_with::<HashMap<String, HashMap<String, u32>>>(
+            "{\"a\": {},\n\"b\": {\"x\": 1, \"x\": 2}}",
+            &options,
+        );
+        let err = result.unwrap_err();
+        assert_eq!(
+            ParserErrKind::DuplicateProperty {
+                key: "x".to_string(),
+                first: Box::new(Span {
+                    start: Position {
+                        line: 2,
+                        column: 7,
+                        column_utf16: 7,
+                        offset: 16,
+                    },
+                    end: Position {
+                        line: 2,
+                        column: 10,
+                        column_utf16: 10,
+                        offset: 19,
+                    },
+                }),
+            },
+            err.kind
+        );
+        assert_eq!(2, err.line);
+    }
 }
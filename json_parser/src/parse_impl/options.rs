@@ -15,7 +15,7 @@ impl<T: Parse> Parse for Option<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::ParserErrKind;
+    use crate::{JsonPath, JsonType, ParserErrKind, Span};
 
     use super::*;
 
@@ -48,9 +48,14 @@ mod tests {
         let result = Parser::parse::<Option<bool>>("5");
         assert_eq!(
             Err(ParserErr {
-                kind: ParserErrKind::UnexpectedToken,
+                kind: ParserErrKind::InvalidType {
+                    expected: "bool",
+                    found: JsonType::Number,
+                },
                 line: 1,
                 lexeme: "5".to_string(),
+                span: Box::new(Span::single_line(0, 1)),
+                path: JsonPath::default(),
             }),
             result
         );
@@ -1,9 +1,23 @@
-use std::str::FromStr;
+use std::{any::type_name, fmt::Display, str::FromStr};
 
 use crate::{Parse, Parser, ParserErr, ParserErrKind, token::TokenKind};
 
 // Define a trait so we can specify which number types we want to be parsable
-pub trait JsonNumber: Sized + FromStr {}
+pub trait JsonNumber: Sized + FromStr + Display {
+    // Integers can only be parsed from numbers without a fraction or exponent
+    const IS_FLOAT: bool = false;
+
+    // JSON has no representation for NaN or infinity, so floats need to be able to say when they
+    // cannot be written out
+    fn is_finite(&self) -> bool {
+        true
+    }
+
+    // Only called for finite numbers
+    fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
 
 impl JsonNumber for i128 {}
 impl JsonNumber for i64 {}
@@ -17,20 +31,54 @@ impl JsonNumber for u32 {}
 impl JsonNumber for u16 {}
 impl JsonNumber for u8 {}
 
-impl JsonNumber for f64 {}
-impl JsonNumber for f32 {}
+// `Display` writes floats out in full, so 1e300 would become a 301 digit number. `Debug` gives the
+// shortest form that parses back to the same value, but adds `.0` to whole numbers
+fn float_to_json(json: String) -> String {
+    match json.strip_suffix(".0") {
+        Some(whole) => whole.to_string(),
+        None => json,
+    }
+}
+
+impl JsonNumber for f64 {
+    const IS_FLOAT: bool = true;
+
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+
+    fn to_json(&self) -> String {
+        float_to_json(format!("{self:?}"))
+    }
+}
+impl JsonNumber for f32 {
+    const IS_FLOAT: bool = true;
+
+    fn is_finite(&self) -> bool {
+        f32::is_finite(*self)
+    }
+
+    fn to_json(&self) -> String {
+        float_to_json(format!("{self:?}"))
+    }
+}
 
 impl<T: JsonNumber> Parse for T {
     fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
         let token = parser.advance()?;
+        if token.kind != TokenKind::Number {
+            return Err(parser.make_type_err(type_name::<T>(), &token));
+        }
 
-        // If we have a number, parse the lexeme (string) as an i32, and return that, or error if failed
-        match token.kind {
-            TokenKind::Number => token
-                .lexeme
-                .parse::<T>()
-                .map_err(|_| parser.make_err_prev(ParserErrKind::InvalidNumber)),
-            _ => Err(parser.make_err_prev(ParserErrKind::UnexpectedToken)),
+        if !T::IS_FLOAT && token.lexeme.contains(['.', 'e', 'E']) {
+            return Err(parser.make_err_prev(ParserErrKind::InvalidNumber));
+        }
+
+        // The scanner has already checked the lexeme is a valid number, so if it can't be parsed,
+        // it must be too big or small (floats become infinite instead of failing)
+        match token.lexeme.parse::<T>() {
+            Ok(value) if value.is_finite() => Ok(value),
+            _ => Err(parser.make_err_prev(ParserErrKind::NumberOutOfRange(type_name::<T>()))),
         }
     }
 }
@@ -41,14 +89,14 @@ impl Parse for bool {
         match token.kind {
             // NOTE: should only be "true" or "false", which is why we can do this
             TokenKind::Bool => Ok(token.lexeme == "true"),
-            _ => Err(parser.make_err_prev(ParserErrKind::UnexpectedToken)),
+            _ => Err(parser.make_type_err("bool", &token)),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ParserErrKind;
+    use crate::{JsonPath, JsonType, ParserErrKind, Span};
 
     use super::*;
 
@@ -69,14 +117,59 @@ mod tests {
         let result = Parser::parse::<u32>("-5");
         assert_eq!(
             Err(ParserErr {
-                kind: ParserErrKind::InvalidNumber,
+                kind: ParserErrKind::NumberOutOfRange("u32"),
                 line: 1,
                 lexeme: "-5".to_string(),
+                span: Box::new(Span::single_line(0, 2)),
+                path: JsonPath::default(),
             }),
             result
         );
     }
 
+    #[test]
+    fn test_out_of_range() {
+        let cases = vec![
+            (Parser::parse::<u8>("300").map(|_| ()), "u8"),
+            (Parser::parse::<i8>("-129").map(|_| ()), "i8"),
+            (Parser::parse::<f32>("1e40").map(|_| ()), "f32"),
+            (Parser::parse::<f64>("-1e400").map(|_| ()), "f64"),
+        ];
+
+        for (result, expected) in cases {
+            assert_eq!(
+                Err(ParserErrKind::NumberOutOfRange(expected)),
+                result.map_err(|x| x.kind)
+            );
+        }
+    }
+
+    #[test]
+    fn test_in_range() {
+        assert_eq!(Ok(255), Parser::parse::<u8>("255"));
+        assert_eq!(Ok(-128), Parser::parse::<i8>("-128"));
+        assert_eq!(Ok(0.0), Parser::parse::<f64>("1e-400"));
+    }
+
+    #[test]
+    fn test_number_invalid_type() {
+        let result = Parser::parse::<u32>(r#""5""#);
+        assert_eq!(
+            Err(ParserErrKind::InvalidType {
+                expected: "u32",
+                found: JsonType::String,
+            }),
+            result.map_err(|x| x.kind)
+        );
+
+        // Not a value at all
+        let result = Parser::parse::<u32>("]");
+        assert_eq!(
+            Err(ParserErrKind::UnexpectedToken),
+            result.map_err(|x| x.kind)
+        );
+    }
+
     #[test]
     fn test_float() {
         let result = Parser::parse::<f32>("-5.1");
@@ -97,6 +190,8 @@ mod tests {
                 kind: ParserErrKind::InvalidNumber,
                 line: 1,
                 lexeme: "5e2".to_string(),
+                span: Box::new(Span::single_line(0, 3)),
+                path: JsonPath::default(),
             }),
             result
         );
@@ -116,9 +211,14 @@ mod tests {
         let result = Parser::parse::<bool>("null");
         assert_eq!(
             Err(ParserErr {
-                kind: ParserErrKind::UnexpectedToken,
+                kind: ParserErrKind::InvalidType {
+                    expected: "bool",
+                    found: JsonType::Null,
+                },
                 line: 1,
                 lexeme: "null".to_string(),
+                span: Box::new(Span::single_line(0, 4)),
+                path: JsonPath::default(),
             }),
             result
         );
@@ -1,19 +1,20 @@
-use crate::{Parse, Parser, ParserErr, ParserErrKind, token::TokenKind};
+use crate::{Parse, Parser, ParserErr, token::TokenKind};
 
 impl Parse for String {
     fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
         // If we have a string, return the value captured by the scanner
         // Otherwise, we expected a string, but didn't get one - error
-        match parser.advance()?.kind {
+        let token = parser.advance()?;
+        match token.kind {
             TokenKind::String(val) => Ok(val),
-            _ => Err(parser.make_err_prev(ParserErrKind::UnexpectedToken)),
+            _ => Err(parser.make_type_err("String", &token)),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ParserErrKind;
+    use crate::{JsonPath, JsonType, ParserErrKind, Span};
 
     use super::*;
 
@@ -23,6 +24,18 @@ mod tests {
         assert_eq!(Ok("test".to_string()), result);
     }
 
+    #[test]
+    fn test_invalid_type() {
+        let result = Parser::parse::<String>("42");
+        assert_eq!(
+            Err(ParserErrKind::InvalidType {
+                expected: "String",
+                found: JsonType::Number,
+            }),
+            result.map_err(|x| x.kind)
+        );
+    }
+
     #[test]
     fn test_valid_escape_sequences() {
         let cases = vec![
@@ -32,6 +45,7 @@ mod tests {
             (r#""\b""#, "\x08"),
             (r#""\/""#, "/"),
             (r#""\\""#, "\\"),
+            (r#""\uD83D\uDE00""#, "😀"),
         ];
 
         for (source, expected) in cases {
@@ -44,7 +58,6 @@ mod tests {
     fn test_invalid_escape_sequences() {
         let cases = vec![
             (r#""\uZZZZ""#, r#""\uZZZZ"#),
-            (r#""\uD800""#, r#""\uD800"#),
             (r#""bad\escape""#, r#""bad\e"#),
         ];
 
@@ -55,9 +68,26 @@ mod tests {
                     kind: ParserErrKind::InvalidEscapeSequence,
                     line: 1,
                     lexeme: error_lexeme.to_string(),
+                    span: Box::new(Span::single_line(0, error_lexeme.len())),
+                    path: JsonPath::default(),
                 }),
                 result
             );
         }
     }
+
+    #[test]
+    fn test_lone_surrogate() {
+        let result = Parser::parse::<String>(r#""\uD800""#);
+        assert_eq!(
+            Err(ParserErr {
+                kind: ParserErrKind::LoneSurrogate,
+                line: 1,
+                lexeme: r#""\uD800"#.to_string(),
+                span: Box::new(Span::single_line(0, 7)),
+                path: JsonPath::default(),
+            }),
+            result
+        );
+    }
 }
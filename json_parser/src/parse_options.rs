@@ -0,0 +1,129 @@
+// What derived structs do with properties that don't match any of their fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownFields {
+    #[default]
+    Deny,
+    Ignore,
+}
+
+// What happens when an object has more than one property with the same key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeys {
+    // Fail with `ParserErrKind::DuplicateProperty`, giving the positions of both
+    Error,
+    // Keep the first value, skipping over the later ones
+    FirstWins,
+    // Each value replaces the one before it
+    #[default]
+    LastWins,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    // Allow raw control characters (U+0000 to U+001F) inside strings, which RFC 8259 forbids
+    pub allow_control_characters: bool,
+
+    // Allow a comma after the last element of an array or property of an object, e.g. `[1, 2,]`
+    pub allow_trailing_commas: bool,
+
+    // Maximum number of arrays and objects that can be nested inside each other. Parsing is
+    // recursive, so this stops deeply nested input from overflowing the stack
+    pub max_depth: usize,
+
+    // Can be overridden by `#[json(deny_unknown_fields)]` or `#[json(ignore_unknown_fields)]`
+    pub unknown_fields: UnknownFields,
+
+    // Applies to every object, whether parsed into a map, `JsonValue` or a derived struct
+    pub duplicate_keys: DuplicateKeys,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            allow_control_characters: false,
+            allow_trailing_commas: false,
+            max_depth: 128,
+            unknown_fields: UnknownFields::Deny,
+            duplicate_keys: DuplicateKeys::LastWins,
+        }
+    }
+}
+
+// Builder methods, e.g. `ParseOptions::new().allow_trailing_commas(true).max_depth(64)`
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow_control_characters(mut self, allow: bool) -> Self {
+        self.allow_control_characters = allow;
+        self
+    }
+
+    pub fn allow_trailing_commas(mut self, allow: bool) -> Self {
+        self.allow_trailing_commas = allow;
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn unknown_fields(mut self, unknown_fields: UnknownFields) -> Self {
+        self.unknown_fields = unknown_fields;
+        self
+    }
+
+    pub fn duplicate_keys(mut self, duplicate_keys: DuplicateKeys) -> Self {
+        self.duplicate_keys = duplicate_keys;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Parse, Parser, ParserErr, json_value::JsonValue};
+
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let options = ParseOptions::new()
+            .allow_control_characters(true)
+            .allow_trailing_commas(true)
+            .max_depth(5)
+            .unknown_fields(UnknownFields::Ignore)
+            .duplicate_keys(DuplicateKeys::Error);
+
+        assert_eq!(
+            ParseOptions {
+                allow_control_characters: true,
+                allow_trailing_commas: true,
+                max_depth: 5,
+                unknown_fields: UnknownFields::Ignore,
+                duplicate_keys: DuplicateKeys::Error,
+            },
+            options
+        );
+    }
+
+    // Custom implementation that changes its behaviour depending on the options
+    struct MaxDepth(usize);
+
+    impl Parse for MaxDepth {
+        fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
+            JsonValue::parse(parser)?;
+            Ok(MaxDepth(parser.options().max_depth))
+        }
+    }
+
+    #[test]
+    fn test_readable_from_parse() {
+        let result = Parser::parse_with::<MaxDepth>("null", &ParseOptions::new().max_depth(7));
+        assert_eq!(7, result.unwrap().0);
+
+        let result = Parser::parse::<MaxDepth>("null");
+        assert_eq!(128, result.unwrap().0);
+    }
+}
@@ -1,117 +1,702 @@
+use std::collections::HashMap;
+
 use crate::{
+    parse_options::{DuplicateKeys, ParseOptions},
+    path::{JsonPath, PathSegment},
     scanner::{Scanner, ScannerErr, ScannerErrKind},
-    token::{Token, TokenKind},
+    token::{Span, Token, TokenKind},
 };
 
 static BUG_PREV_BEFORE_ADVANCE: &str = "[BUG] Called `prev` before advancing - no previous value";
 static BUG_NO_TOKEN_ERR_REPORT: &str = "[BUG] Failed to get token for reporting error";
+static BUG_RESCAN_KEY: &str = "[BUG] Key failed to scan again after being skipped";
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ParserErr {
     pub kind: ParserErrKind,
     pub line: usize,
+    // The source text of `span`, except for errors spanning a whole object, where copying all of
+    // it out would be wasteful, so it is only the opening `{`
     pub lexeme: String,
+    // The largest part of the error, so boxed to keep `Result<T, ParserErr>` small
+    pub span: Box<Span>,
+    pub path: JsonPath,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ParserErrKind {
     // Scanner specific errors
-    UnterminatedString,
     UnrecognisedSymbol,
     UnrecognisedLiteral,
-    InvalidNumber,
     InvalidEscapeSequence,
+    LoneSurrogate,
+    ControlCharacterInString(char),
+    LeadingZero,
+    MissingIntegerDigits,
+    MissingFractionDigits,
+    MissingExponentDigits,
+    UnexpectedCharacterInNumber,
 
     // Parser specific errors
     ExpectedEndOfSource,
     ExpectedToken(TokenKind),
     UnexpectedToken,
+    // Number is valid JSON, but can't be parsed into the requested type (e.g. `5e2` into `i32`)
+    InvalidNumber,
+    // Number is too big or small for the requested type (e.g. `300` into `u8`)
+    NumberOutOfRange(&'static str),
+    // Value is valid JSON, but not the type requested (e.g. a string into `u32`)
+    InvalidType {
+        expected: &'static str,
+        found: JsonType,
+    },
     UnknownProperty,
-    MissingProperty(String),
+    // Properties missing from an object, all reported together across the whole object
+    MissingProperties(Vec<String>),
+    // Property has the same key as an earlier one in the object, which was at `first`. Boxed to
+    // keep errors small, as they are returned through every level of nesting
+    DuplicateProperty {
+        key: String,
+        first: Box<Span>,
+    },
+    // String doesn't match any of the unit variants of an enum
+    UnknownVariant {
+        found: String,
+        expected: &'static [&'static str],
+    },
+    // Value doesn't match any of the variants of an untagged enum
+    NoMatchingVariant(&'static str),
+    // Array doesn't have the number of elements needed, e.g. for a tuple struct
+    InvalidLength {
+        expected: usize,
+        found: usize,
+    },
+    // Arrays and objects are nested deeper than `ParseOptions::max_depth`
+    DepthLimitExceeded,
 
     // Both
     UnexpectedEndOfSource,
 }
 
+impl ParserErrKind {
+    // Errors from malformed JSON or from nesting too deeply, which would happen whatever type the
+    // value was parsed as, so there is no point trying another one (e.g. for untagged enums)
+    pub fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            Self::UnrecognisedSymbol
+                | Self::UnrecognisedLiteral
+                | Self::InvalidEscapeSequence
+                | Self::LoneSurrogate
+                | Self::ControlCharacterInString(_)
+                | Self::LeadingZero
+                | Self::MissingIntegerDigits
+                | Self::MissingFractionDigits
+                | Self::MissingExponentDigits
+                | Self::UnexpectedCharacterInNumber
+                | Self::DepthLimitExceeded
+                | Self::UnexpectedEndOfSource
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonType {
+    Object,
+    Array,
+    String,
+    Number,
+    Bool,
+    Null,
+}
+
+impl JsonType {
+    // Type of the value starting with this token, or `None` if a value can't start with it
+    pub fn of(kind: &TokenKind) -> Option<Self> {
+        match kind {
+            TokenKind::LCurlyBracket => Some(Self::Object),
+            TokenKind::LBracket => Some(Self::Array),
+            TokenKind::String(_) => Some(Self::String),
+            TokenKind::Number => Some(Self::Number),
+            TokenKind::Bool => Some(Self::Bool),
+            TokenKind::Null => Some(Self::Null),
+            _ => None,
+        }
+    }
+}
+
 // Convert ScannerErr to ParserErr (easy 1 to 1 mapping)
 impl From<ScannerErr> for ParserErr {
     fn from(err: ScannerErr) -> Self {
         let kind = match err.kind {
             ScannerErrKind::UnexpectedEndOfSource => ParserErrKind::UnexpectedEndOfSource,
-            ScannerErrKind::UnterminatedString => ParserErrKind::UnterminatedString,
             ScannerErrKind::UnrecognisedSymbol => ParserErrKind::UnrecognisedSymbol,
             ScannerErrKind::UnrecognisedLiteral => ParserErrKind::UnrecognisedLiteral,
-            ScannerErrKind::InvalidNumber => ParserErrKind::InvalidNumber,
             ScannerErrKind::InvalidEscapeSequence => ParserErrKind::InvalidEscapeSequence,
+            ScannerErrKind::LoneSurrogate => ParserErrKind::LoneSurrogate,
+            ScannerErrKind::ControlCharacterInString(chr) => {
+                ParserErrKind::ControlCharacterInString(chr)
+            }
+            ScannerErrKind::LeadingZero => ParserErrKind::LeadingZero,
+            ScannerErrKind::MissingIntegerDigits => ParserErrKind::MissingIntegerDigits,
+            ScannerErrKind::MissingFractionDigits => ParserErrKind::MissingFractionDigits,
+            ScannerErrKind::MissingExponentDigits => ParserErrKind::MissingExponentDigits,
+            ScannerErrKind::UnexpectedCharacterInNumber => {
+                ParserErrKind::UnexpectedCharacterInNumber
+            }
         };
 
         Self {
             line: err.line,
             lexeme: err.lexeme,
+            span: Box::new(err.span),
+            path: JsonPath::default(),
             kind,
         }
     }
 }
 
+// An array or object inside a value being skipped, from `Parser::skip_value`
+struct SkippedContainer {
+    is_object: bool,
+    // Number of elements started so far
+    len: usize,
+    // Span of the key of the current element, for objects
+    key: Option<Span>,
+    // Whether the current element's value has been started, so errors are inside of it
+    in_value: bool,
+}
+
+// Position to rewind the parser back to, from `Parser::checkpoint`
+#[derive(Debug, Clone)]
+pub struct Checkpoint<'a> {
+    scanner: Scanner<'a>,
+    prev: Option<Token>,
+    current: Option<Token>,
+    scanner_err: Option<ScannerErr>,
+    path_len: usize,
+    depth: usize,
+    // Rather than copying the keys found so far, only enough is kept to undo any added later
+    keys_len: usize,
+    innermost_keys_len: usize,
+    offset: usize,
+}
+
 pub trait Parse {
     fn parse(parser: &mut Parser) -> Result<Self, ParserErr>
     where
         Self: Sized;
 }
 
+// Types parsed from the properties of an object, which can be shared with other types in the same
+// object. The derive implements this for structs, so they can be used with `#[json(flatten)]`
+pub trait ParseFields: Sized {
+    // Values of the fields found so far
+    type Fields;
+
+    fn empty_fields() -> Self::Fields;
+
+    // Parse the value of the property `key` if it is one of the fields, giving whether it was
+    fn parse_field(
+        fields: &mut Self::Fields,
+        key: &str,
+        parser: &mut Parser,
+    ) -> Result<bool, ParserErr>;
+
+    // Build the value once the object has ended. If any required fields are missing, their names
+    // are added to `missing` instead, so the parent object can report them along with its own
+    fn finish_fields(fields: Self::Fields, missing: &mut Vec<&'static str>) -> Option<Self>;
+}
+
 #[derive(Debug, Clone)]
 pub struct Parser<'a> {
     scanner: Scanner<'a>,
 
     prev: Option<Token>,
     current: Option<Token>,
+
+    // The scanner runs a token ahead, so hold on to any error until the parser reaches it. This
+    // means it is reported with the path of the value it is in, rather than the previous one
+    scanner_err: Option<ScannerErr>,
+
+    path: Vec<PathSegment>,
+
+    options: &'a ParseOptions,
+    depth: usize,
+
+    // Keys found so far in each object being parsed, and where they were. Only kept if
+    // duplicates have to be found, for `ParseOptions::duplicate_keys`
+    keys: Vec<HashMap<String, Span>>,
 }
 
-impl Parser<'_> {
+impl<'a> Parser<'a> {
     pub fn make_err(&self, kind: ParserErrKind) -> ParserErr {
         // Get current token, fallback to previous
         let err_token = self
             .current
-            .clone()
-            .unwrap_or_else(|| self.prev.clone().expect(BUG_NO_TOKEN_ERR_REPORT));
+            .as_ref()
+            .unwrap_or_else(|| self.prev.as_ref().expect(BUG_NO_TOKEN_ERR_REPORT));
 
-        ParserErr {
-            kind,
-            line: err_token.line,
-            lexeme: err_token.lexeme,
-        }
+        self.make_err_from_token(kind, err_token)
     }
 
     pub fn make_err_from_token(&self, kind: ParserErrKind, token: &Token) -> ParserErr {
+        // Taken from the source, as the tokens of skipped strings don't keep their lexeme
+        let span = token.span;
         ParserErr {
             kind,
             line: token.line,
-            lexeme: token.lexeme.to_owned(),
+            lexeme: self.scanner.source()[span.start.offset..span.end.offset].to_string(),
+            span: Box::new(token.span),
+            path: self.path(),
         }
     }
 
     // Make err with prev token instead of current
     pub fn make_err_prev(&self, kind: ParserErrKind) -> ParserErr {
-        let err_token = self.prev.clone().expect(BUG_NO_TOKEN_ERR_REPORT);
+        let err_token = self.prev.as_ref().expect(BUG_NO_TOKEN_ERR_REPORT);
+        self.make_err_from_token(kind, err_token)
+    }
+
+    // Call straight after consuming the end of an object starting at `l_curly_token`, which is
+    // missing the properties `missing`
+    pub fn make_missing_err(&self, missing: &[&str], l_curly_token: &Token) -> ParserErr {
+        let r_curly_token = self.prev.as_ref().expect(BUG_PREV_BEFORE_ADVANCE);
+        let span = Span {
+            start: l_curly_token.span.start,
+            end: r_curly_token.span.end,
+        };
 
         ParserErr {
-            kind,
-            line: err_token.line,
-            lexeme: err_token.lexeme,
+            kind: ParserErrKind::MissingProperties(
+                missing.iter().map(|name| name.to_string()).collect(),
+            ),
+            line: l_curly_token.line,
+            lexeme: l_curly_token.lexeme.clone(),
+            span: Box::new(span),
+            path: self.path(),
+        }
+    }
+
+    // Use when `token` should have been the start of an `expected` value
+    // If it is a different type of value report that, otherwise it is not a value at all
+    pub fn make_type_err(&self, expected: &'static str, token: &Token) -> ParserErr {
+        match JsonType::of(&token.kind) {
+            Some(found) => {
+                self.make_err_from_token(ParserErrKind::InvalidType { expected, found }, token)
+            }
+            None => self.make_err_from_token(ParserErrKind::UnexpectedToken, token),
+        }
+    }
+
+    // Call straight after consuming the opening token of an array or object, and call
+    // `exit_nested` once the closing token has been consumed
+    pub fn enter_nested(&mut self) -> Result<(), ParserErr> {
+        self.depth += 1;
+        if self.depth > self.options.max_depth {
+            return Err(self.make_err_prev(ParserErrKind::DepthLimitExceeded));
+        }
+
+        Ok(())
+    }
+
+    pub fn exit_nested(&mut self) {
+        self.depth -= 1;
+    }
+
+    // Containers push the key or index of the value they are about to parse, and pop it once
+    // parsed, so that errors can report where in the JSON they happened
+    pub fn push_path(&mut self, segment: PathSegment) {
+        self.path.push(segment);
+    }
+
+    pub fn pop_path(&mut self) {
+        self.path.pop();
+    }
+
+    // Call after `enter_nested` for an object, and call `end_object` before `exit_nested`, so
+    // that `check_key` can find duplicate keys
+    pub fn start_object(&mut self) {
+        if self.options.duplicate_keys != DuplicateKeys::LastWins {
+            self.keys.push(HashMap::new());
+        }
+    }
+
+    pub fn end_object(&mut self) {
+        if self.options.duplicate_keys != DuplicateKeys::LastWins {
+            self.keys.pop();
+        }
+    }
+
+    // Call for each property of an object, with the token of its key. Gives false if its value
+    // should be skipped, because an earlier property with the same key takes priority
+    pub fn check_key(&mut self, key_token: &Token) -> Result<bool, ParserErr> {
+        let TokenKind::String(key) = &key_token.kind else {
+            return Ok(true);
+        };
+        let Some(keys) = self.keys.last_mut() else {
+            return Ok(true);
+        };
+
+        let Some(&first) = keys.get(key) else {
+            keys.insert(key.clone(), key_token.span);
+            return Ok(true);
+        };
+
+        match self.options.duplicate_keys {
+            DuplicateKeys::Error => Err(self.make_err_from_token(
+                ParserErrKind::DuplicateProperty {
+                    key: key.clone(),
+                    first: Box::new(first),
+                },
+                key_token,
+            )),
+            DuplicateKeys::FirstWins => Ok(false),
+            DuplicateKeys::LastWins => Ok(true),
+        }
+    }
+
+    pub fn path(&self) -> JsonPath {
+        JsonPath(self.path.clone())
+    }
+
+    pub fn options(&self) -> &ParseOptions {
+        self.options
+    }
+
+    // Save the current position, so that `restore` can go back to it if parsing fails, e.g. to
+    // try parsing the same value as a different type. It must be restored before the end of the
+    // object it was taken in, if any
+    pub fn checkpoint(&self) -> Checkpoint<'a> {
+        Checkpoint {
+            scanner: self.scanner.clone(),
+            prev: self.prev.clone(),
+            current: self.current.clone(),
+            scanner_err: self.scanner_err.clone(),
+            path_len: self.path.len(),
+            depth: self.depth,
+            keys_len: self.keys.len(),
+            innermost_keys_len: self.keys.last().map_or(0, HashMap::len),
+            offset: self
+                .current
+                .as_ref()
+                .map_or(self.scanner.source().len(), |token| token.span.start.offset),
+        }
+    }
+
+    pub fn restore(&mut self, checkpoint: &Checkpoint<'a>) {
+        self.scanner = checkpoint.scanner.clone();
+        self.prev = checkpoint.prev.clone();
+        self.current = checkpoint.current.clone();
+        self.scanner_err = checkpoint.scanner_err.clone();
+        // Anything pushed since the checkpoint may not have been popped if parsing failed
+        self.path.truncate(checkpoint.path_len);
+        self.depth = checkpoint.depth;
+
+        // Objects started since the checkpoint are dropped, and any keys found since then in the
+        // object it was taken in are after its position
+        self.keys.truncate(checkpoint.keys_len);
+        if let Some(keys) = self.keys.last_mut()
+            && keys.len() > checkpoint.innermost_keys_len
+        {
+            keys.retain(|_, span| span.start.offset < checkpoint.offset);
+        }
+    }
+
+    // Call once every variant of an untagged enum has failed, after restoring to the start of the
+    // value. If one of them got into the value before failing, its error is more useful than
+    // being told nothing matched, so the one that got furthest is returned
+    pub fn make_no_match_err(&self, errors: Vec<ParserErr>, expected: &'static str) -> ParserErr {
+        let no_match = self.make_err(ParserErrKind::NoMatchingVariant(expected));
+        let furthest = errors
+            .into_iter()
+            .rev()
+            .max_by_key(|err| err.span.start.offset);
+
+        match furthest {
+            Some(err) if err.span.start.offset > no_match.span.start.offset => err,
+            _ => no_match,
+        }
+    }
+
+    // Look through the object at the current token for the property `tag`, and return its value
+    // if it is one of `variants`. Nothing is consumed, so the object can then be parsed knowing
+    // which variant it is. `expected` is the type name reported if it isn't an object
+    pub fn find_tag(
+        &mut self,
+        tag: &str,
+        variants: &'static [&'static str],
+        expected: &'static str,
+    ) -> Result<String, ParserErr> {
+        let checkpoint = self.checkpoint();
+        let result = self.find_tag_in_object(tag, variants, expected);
+        self.restore(&checkpoint);
+        result
+    }
+
+    // Consume the next value, checking it is valid JSON but without building anything from it.
+    // Used for values which will be thrown away, such as unknown properties
+    // Strings are not unescaped, so the path into the value is only worked out for errors
+    pub fn skip_value(&mut self) -> Result<(), ParserErr> {
+        let mut containers = Vec::new();
+
+        self.scanner.raw_strings(true);
+        let result = self.skip_tokens(&mut containers);
+        self.scanner.raw_strings(false);
+
+        result.map_err(|mut err| {
+            for container in containers.iter().filter(|container| container.in_value) {
+                let segment = match container.key {
+                    Some(key) => PathSegment::Key(self.unescape_key(key)),
+                    None => PathSegment::Index(container.len - 1),
+                };
+                err.path.0.push(segment);
+            }
+            err
+        })
+    }
+
+    // Walks through the tokens of the value iteratively, so deeply nested values can't overflow
+    // the stack. `containers` keeps track of each array or object the value is inside of
+    fn skip_tokens(&mut self, containers: &mut Vec<SkippedContainer>) -> Result<(), ParserErr> {
+        loop {
+            // Start of a value
+            let token = self.advance()?;
+            match token.kind {
+                TokenKind::String(_) | TokenKind::Number | TokenKind::Bool | TokenKind::Null => {}
+                TokenKind::LBracket | TokenKind::LCurlyBracket => {
+                    self.enter_nested()?;
+
+                    let is_object = token.kind == TokenKind::LCurlyBracket;
+                    if !self.check(Self::closing_token(is_object))? {
+                        let mut container = SkippedContainer {
+                            is_object,
+                            len: 0,
+                            key: None,
+                            in_value: false,
+                        };
+                        self.skip_to_element(&mut container)?;
+                        containers.push(container);
+                        continue;
+                    }
+
+                    self.advance()?;
+                    self.exit_nested();
+                }
+                _ => return Err(self.make_err_prev(ParserErrKind::UnexpectedToken)),
+            }
+
+            // End of a value, so close each container that has no more elements
+            loop {
+                let Some(container) = containers.last_mut() else {
+                    return Ok(());
+                };
+                container.in_value = false;
+                let closing = Self::closing_token(container.is_object);
+
+                if self.check(TokenKind::Comma)? {
+                    self.advance()?;
+
+                    if !self.check(closing.clone())? {
+                        self.skip_to_element(container)?;
+                        break;
+                    }
+
+                    // No trailing comma, unless the options allow it
+                    if !self.options.allow_trailing_commas {
+                        return Err(self.make_err_prev(ParserErrKind::UnexpectedToken));
+                    }
+                }
+
+                self.consume(closing)?;
+                self.exit_nested();
+                containers.pop();
+            }
+        }
+    }
+
+    fn closing_token(is_object: bool) -> TokenKind {
+        if is_object {
+            TokenKind::RCurlyBracket
+        } else {
+            TokenKind::RBracket
+        }
+    }
+
+    // Move past the key of the next element of `container`, if it is an object
+    fn skip_to_element(&mut self, container: &mut SkippedContainer) -> Result<(), ParserErr> {
+        if container.is_object {
+            let key_token = self.advance()?;
+            if !matches!(key_token.kind, TokenKind::String(_)) {
+                return Err(self.make_err_prev(ParserErrKind::UnexpectedToken));
+            }
+            self.consume(TokenKind::Colon)?;
+            container.key = Some(key_token.span);
+        }
+
+        container.len += 1;
+        container.in_value = true;
+        Ok(())
+    }
+
+    // The key of a skipped object was scanned as a raw string, so scan it again for its value
+    fn unescape_key(&self, span: Span) -> String {
+        let mut scanner = Scanner::init(&self.scanner.source()[span.start.offset..span.end.offset]);
+        scanner.allow_control_characters(self.options.allow_control_characters);
+
+        match scanner.next_token() {
+            Ok(Some(Token {
+                kind: TokenKind::String(key),
+                ..
+            })) => key,
+            _ => panic!("{BUG_RESCAN_KEY}"),
+        }
+    }
+
+    // Move on to the element at `index` of an array which must have `len` elements, such as a
+    // tuple. The array started at `l_bracket_token`, which is where a wrong length is reported
+    pub fn tuple_element(
+        &mut self,
+        index: usize,
+        len: usize,
+        l_bracket_token: &Token,
+    ) -> Result<(), ParserErr> {
+        let too_short = self.make_err_from_token(
+            ParserErrKind::InvalidLength {
+                expected: len,
+                found: index,
+            },
+            l_bracket_token,
+        );
+
+        if self.check(TokenKind::RBracket)? {
+            return Err(too_short);
+        }
+        if index > 0 {
+            self.consume(TokenKind::Comma)?;
+
+            // A trailing comma doesn't start another element
+            if self.options.allow_trailing_commas && self.check(TokenKind::RBracket)? {
+                return Err(too_short);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Consume the end of an array which must have `len` elements, after the last of them. Any
+    // more elements are skipped over, so that the error has the actual length
+    pub fn end_tuple(&mut self, len: usize, l_bracket_token: &Token) -> Result<(), ParserErr> {
+        let mut found = len;
+        while self.check(TokenKind::Comma)? {
+            self.advance()?;
+            if self.options.allow_trailing_commas && self.check(TokenKind::RBracket)? {
+                break;
+            }
+
+            self.skip_value()?;
+            found += 1;
+        }
+        self.consume(TokenKind::RBracket)?;
+
+        if found != len {
+            return Err(self.make_err_from_token(
+                ParserErrKind::InvalidLength {
+                    expected: len,
+                    found,
+                },
+                l_bracket_token,
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn find_tag_in_object(
+        &mut self,
+        tag: &str,
+        variants: &'static [&'static str],
+        expected: &'static str,
+    ) -> Result<String, ParserErr> {
+        let l_curly_token = self.advance()?;
+        if l_curly_token.kind != TokenKind::LCurlyBracket {
+            return Err(self.make_type_err(expected, &l_curly_token));
+        }
+        self.enter_nested()?;
+
+        let mut found = None;
+
+        // Other properties are checked properly once the variant is known, so only need skipping
+        while !self.check(TokenKind::RCurlyBracket)? {
+            let key = match self.advance()?.kind {
+                TokenKind::String(key) => key,
+                _ => return Err(self.make_err_prev(ParserErrKind::UnexpectedToken)),
+            };
+            self.consume(TokenKind::Colon)?;
+
+            self.push_path(PathSegment::Key(key.clone()));
+            if key == tag {
+                let token = self.advance()?;
+                let value = match token.kind {
+                    TokenKind::String(ref value) if variants.contains(&value.as_str()) => {
+                        value.clone()
+                    }
+                    TokenKind::String(ref value) => {
+                        return Err(self.make_err_from_token(
+                            ParserErrKind::UnknownVariant {
+                                found: value.clone(),
+                                expected: variants,
+                            },
+                            &token,
+                        ));
+                    }
+                    _ => return Err(self.make_type_err("String", &token)),
+                };
+
+                // Otherwise duplicates are either an error, or this is the tag that's used
+                if self.options.duplicate_keys != DuplicateKeys::LastWins {
+                    return Ok(value);
+                }
+                found = Some(value);
+            } else {
+                self.skip_value()?;
+            }
+            self.pop_path();
+
+            if !self.check(TokenKind::Comma)? {
+                break;
+            }
+            self.advance()?;
+        }
+
+        if found.is_none() {
+            self.consume(TokenKind::RCurlyBracket)?;
         }
+        found.ok_or_else(|| self.make_missing_err(&[tag], &l_curly_token))
     }
 
     pub fn parse<T: Parse>(source: &str) -> Result<T, ParserErr> {
+        Self::parse_with(source, &ParseOptions::default())
+    }
+
+    pub fn parse_with<T: Parse>(source: &str, options: &ParseOptions) -> Result<T, ParserErr> {
         let mut scanner = Scanner::init(source);
-        let current = scanner.next_token()?;
+        scanner.allow_control_characters(options.allow_control_characters);
 
         let mut parser = Parser {
             scanner,
-            current,
+            current: None,
             prev: None,
+            scanner_err: None,
+            path: Vec::new(),
+            options,
+            depth: 0,
+            keys: Vec::new(),
         };
+        parser.scan_next();
 
         let result = T::parse(&mut parser)?;
+        parser.check_scanner_err()?;
         if parser.current.is_some() {
             return Err(parser.make_err(ParserErrKind::ExpectedEndOfSource));
         }
@@ -119,27 +704,57 @@ impl Parser<'_> {
         Ok(result)
     }
 
+    fn scan_next(&mut self) {
+        match self.scanner.next_token() {
+            Ok(token) => self.current = token,
+            Err(err) => {
+                self.current = None;
+                self.scanner_err = Some(err);
+            }
+        }
+    }
+
+    fn check_scanner_err(&self) -> Result<(), ParserErr> {
+        match &self.scanner_err {
+            Some(err) => Err(ParserErr {
+                path: self.path(),
+                ..err.clone().into()
+            }),
+            None => Ok(()),
+        }
+    }
+
     pub fn consume(&mut self, kind: TokenKind) -> Result<Token, ParserErr> {
         if self.check(kind.clone())? {
             return self.advance();
         }
 
+        // Report an invalid token over the token we expected
+        self.check_scanner_err()?;
         Err(self.make_err(ParserErrKind::ExpectedToken(kind)))
     }
 
+    // An invalid token is not any kind, so is only reported once something tries to use it
     pub fn check(&self, kind: TokenKind) -> Result<bool, ParserErr> {
+        if self.scanner_err.is_some() {
+            return Ok(false);
+        }
+
         Ok(self.peek()?.kind == kind)
     }
 
     pub fn peek(&self) -> Result<Token, ParserErr> {
+        self.check_scanner_err()?;
         self.current
             .clone()
-            .ok_or(self.make_err(ParserErrKind::UnexpectedEndOfSource))
+            .ok_or_else(|| self.make_err(ParserErrKind::UnexpectedEndOfSource))
     }
 
     pub fn advance(&mut self) -> Result<Token, ParserErr> {
+        self.check_scanner_err()?;
+
         self.prev = self.current.clone();
-        self.current = self.scanner.next_token()?;
+        self.scan_next();
 
         Ok(self.previous())
     }
@@ -217,7 +832,10 @@ mod tests {
             ("[,]", ParserErrKind::UnexpectedToken),
             ("{", ParserErrKind::UnexpectedEndOfSource),
             ("{} []", ParserErrKind::ExpectedEndOfSource),
-            ("1234a", ParserErrKind::InvalidNumber),
+            ("1234a", ParserErrKind::UnexpectedCharacterInNumber),
+            ("0123", ParserErrKind::LeadingZero),
+            ("[1.]", ParserErrKind::MissingFractionDigits),
+            ("[-.5]", ParserErrKind::MissingIntegerDigits),
             (r#"["trailing", "comma",]"#, ParserErrKind::UnexpectedToken),
             (r#"{"trailing": "comma",}"#, ParserErrKind::UnexpectedToken),
             (
@@ -242,16 +860,20 @@ mod tests {
             (r#"{"a": null,}"#, ParserErrKind::UnexpectedToken),
             ("tru", ParserErrKind::UnrecognisedLiteral),
             ("nulll", ParserErrKind::UnrecognisedLiteral),
-            ("[--1]", ParserErrKind::InvalidNumber),
+            ("[--1]", ParserErrKind::MissingIntegerDigits),
             ("[+1]", ParserErrKind::UnrecognisedSymbol),
             (r#"{null: "value"}"#, ParserErrKind::UnexpectedToken),
             (r#"{"key": undefined}"#, ParserErrKind::UnrecognisedLiteral),
             (r#""\uZZZZ""#, ParserErrKind::InvalidEscapeSequence),
             (
                 r#"{"\uD800": "high surrogate only"}"#,
-                ParserErrKind::InvalidEscapeSequence,
+                ParserErrKind::LoneSurrogate,
             ),
             (r#""bad\escape""#, ParserErrKind::InvalidEscapeSequence),
+            (
+                "[\"raw\ttab\"]",
+                ParserErrKind::ControlCharacterInString('\t'),
+            ),
         ];
 
         for (source, expected) in cases {
@@ -263,4 +885,349 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_allow_control_characters() {
+        let source = "{\"legacy\": \"raw\ttab\r\n\"}";
+        let options = ParseOptions::new().allow_control_characters(true);
+
+        let result = Parser::parse_with::<JsonValue>(source, &options);
+        assert_eq!(
+            Ok(JsonValue::Object(HashMap::from([(
+                "legacy".to_string(),
+                JsonValue::String("raw\ttab\r\n".to_string())
+            )]))),
+            result
+        );
+    }
+
+    #[test]
+    fn test_error_position() {
+        let source = r#"{"a": [1, 2, 3], "b": {"c": true, "d" null}}"#;
+        let result = Parser::parse::<JsonValue>(source).unwrap_err();
+
+        assert_eq!(ParserErrKind::ExpectedToken(TokenKind::Colon), result.kind);
+        assert_eq!(38, result.span.start.offset);
+        assert_eq!(39, result.span.start.column);
+        assert_eq!(42, result.span.end.offset);
+        assert_eq!("null", result.lexeme);
+    }
+
+    #[test]
+    fn test_error_path() {
+        let cases = vec![
+            ("[1, 2, tru]", "$[2]"),
+            (r#"{"a": {"b": [0, {"c": 01}]}}"#, "$.a.b[1].c"),
+            (r#"{"first name": -}"#, r#"$["first name"]"#),
+            // Errors after a value has been parsed belong to the container, not the value
+            ("[1, 2 3]", "$"),
+            (r#"{"a": [1, 2,]}"#, "$.a"),
+        ];
+
+        for (source, expected) in cases {
+            let result = Parser::parse::<JsonValue>(source).unwrap_err();
+            assert_eq!(expected, result.path.to_string(), "Path for: {source}");
+        }
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        // Would overflow the stack without a limit
+        let source = "[".repeat(100_000);
+        let result = Parser::parse::<JsonValue>(&source).unwrap_err();
+        assert_eq!(ParserErrKind::DepthLimitExceeded, result.kind);
+        assert_eq!(Span::single_line(128, 129), *result.span);
+
+        let source = r#"{"a": "#.repeat(100_000);
+        let result = Parser::parse::<JsonValue>(&source).unwrap_err();
+        assert_eq!(ParserErrKind::DepthLimitExceeded, result.kind);
+    }
+
+    #[test]
+    fn test_custom_depth_limit() {
+        let options = ParseOptions::new().max_depth(2);
+
+        let result = Parser::parse_with::<JsonValue>(r#"[{"a": 1}, [], {}]"#, &options);
+        assert!(result.is_ok());
+
+        let result = Parser::parse_with::<JsonValue>(r#"[{"a": []}]"#, &options);
+        assert_eq!(
+            Err(ParserErrKind::DepthLimitExceeded),
+            result.map_err(|x| x.kind)
+        );
+
+        // Depth is counted from the top level, so the limit is not affected by earlier siblings
+        let source = format!("[{}]", "{}, ".repeat(1000) + "[]");
+        let result = Parser::parse_with::<JsonValue>(&source, &options);
+        assert!(result.is_ok());
+    }
+
+    // Tries parsing a number, and falls back to a string
+    #[derive(Debug, PartialEq)]
+    enum NumberOrString {
+        Number(i32),
+        String(String),
+    }
+
+    impl Parse for NumberOrString {
+        fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
+            let checkpoint = parser.checkpoint();
+            if let Ok(number) = i32::parse(parser) {
+                return Ok(NumberOrString::Number(number));
+            }
+
+            parser.restore(&checkpoint);
+            Ok(NumberOrString::String(String::parse(parser)?))
+        }
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let result = Parser::parse::<Vec<NumberOrString>>(r#"[1, "a", 2]"#);
+        assert_eq!(
+            Ok(vec![
+                NumberOrString::Number(1),
+                NumberOrString::String("a".to_string()),
+                NumberOrString::Number(2),
+            ]),
+            result
+        );
+
+        // Errors after restoring are reported at the right place
+        let err = Parser::parse::<Vec<NumberOrString>>("[1, true]").unwrap_err();
+        assert_eq!(
+            ParserErrKind::InvalidType {
+                expected: "String",
+                found: JsonType::Bool,
+            },
+            err.kind
+        );
+        assert_eq!("$[1]", err.path.to_string());
+    }
+
+    // Parses the properties of `{"a": 1, "b": 2}` twice, going back to the start in between
+    struct Rewound;
+
+    impl Parse for Rewound {
+        fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
+            parser.consume(TokenKind::LCurlyBracket)?;
+            parser.enter_nested()?;
+            parser.start_object();
+
+            let checkpoint = parser.checkpoint();
+            for _ in 0..2 {
+                parser.restore(&checkpoint);
+                for i in 0..2 {
+                    let key_token = parser.advance()?;
+                    parser.check_key(&key_token)?;
+                    parser.consume(TokenKind::Colon)?;
+                    i32::parse(parser)?;
+                    if i == 0 {
+                        parser.consume(TokenKind::Comma)?;
+                    }
+                }
+            }
+
+            parser.consume(TokenKind::RCurlyBracket)?;
+            parser.end_object();
+            parser.exit_nested();
+            Ok(Rewound)
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_keys() {
+        // Keys found after the checkpoint are forgotten when it is restored, so aren't duplicates
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeys::Error);
+        let result = Parser::parse_with::<Rewound>(r#"{"a": 1, "b": 2}"#, &options);
+        assert!(result.is_ok());
+    }
+
+    // Finds the tag, then checks nothing was consumed by parsing the whole object
+    #[derive(Debug)]
+    struct Tag(String);
+
+    impl Parse for Tag {
+        fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
+            let tag = parser.find_tag("type", &["a", "b"], "Tag")?;
+            JsonValue::parse(parser)?;
+            Ok(Tag(tag))
+        }
+    }
+
+    #[test]
+    fn test_find_tag() {
+        let result = Parser::parse::<Tag>(r#"{"x": [1, {"type": "c"}], "type": "b"}"#);
+        assert_eq!("b", result.unwrap().0);
+
+        let err = Parser::parse::<Tag>(r#"{"x": 1, "type": "c"}"#).unwrap_err();
+        assert_eq!(
+            ParserErrKind::UnknownVariant {
+                found: "c".to_string(),
+                expected: &["a", "b"],
+            },
+            err.kind
+        );
+        assert_eq!("$.type", err.path.to_string());
+
+        let err = Parser::parse::<Tag>(r#"{"x": 1}"#).unwrap_err();
+        assert_eq!(
+            ParserErrKind::MissingProperties(vec!["type".to_string()]),
+            err.kind
+        );
+
+        let err = Parser::parse::<Tag>("[]").unwrap_err();
+        assert_eq!(
+            ParserErrKind::InvalidType {
+                expected: "Tag",
+                found: JsonType::Array,
+            },
+            err.kind
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Pair(u32, u32);
+
+    impl Parse for Pair {
+        fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
+            let l_bracket_token = parser.consume(TokenKind::LBracket)?;
+            parser.tuple_element(0, 2, &l_bracket_token)?;
+            let first = u32::parse(parser)?;
+            parser.tuple_element(1, 2, &l_bracket_token)?;
+            let second = u32::parse(parser)?;
+            parser.end_tuple(2, &l_bracket_token)?;
+            Ok(Pair(first, second))
+        }
+    }
+
+    #[test]
+    fn test_tuple() {
+        assert_eq!(Ok(Pair(1, 2)), Parser::parse::<Pair>("[1, 2]"));
+
+        let cases = vec![
+            ("[]", 0),
+            ("[1]", 1),
+            ("[1, 2, 3]", 3),
+            ("[1, 2, [3], {}]", 4),
+        ];
+        for (source, found) in cases {
+            let err = Parser::parse::<Pair>(source).unwrap_err();
+            assert_eq!(
+                ParserErrKind::InvalidLength { expected: 2, found },
+                err.kind
+            );
+            assert_eq!("[", err.lexeme);
+        }
+
+        // Trailing commas aren't counted as elements
+        let options = ParseOptions::new().allow_trailing_commas(true);
+        assert_eq!(
+            Ok(Pair(1, 2)),
+            Parser::parse_with::<Pair>("[1, 2,]", &options)
+        );
+        let err = Parser::parse_with::<Pair>("[1,]", &options).unwrap_err();
+        assert_eq!(
+            ParserErrKind::InvalidLength {
+                expected: 2,
+                found: 1,
+            },
+            err.kind
+        );
+
+        let err = Parser::parse::<Pair>("[1, 2,]").unwrap_err();
+        assert_eq!(ParserErrKind::UnexpectedToken, err.kind);
+    }
+
+    // Skips the value, and checks nothing after it was consumed
+    struct Skipped;
+
+    impl Parse for Skipped {
+        fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
+            parser.skip_value()?;
+            Ok(Skipped)
+        }
+    }
+
+    #[test]
+    fn test_skip_value() {
+        let cases = vec![
+            "1",
+            r#""a""#,
+            "null",
+            "[]",
+            "{}",
+            r#"[1, [2, {"a": [3]}], {"b": {}}]"#,
+            r#"{"a": {"b": [1, 2, {"c": null}]}, "d": true}"#,
+        ];
+
+        for source in cases {
+            assert!(Parser::parse::<Skipped>(source).is_ok(), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_skip_value_invalid() {
+        let cases = vec![
+            ("[1, 2,]", ParserErrKind::UnexpectedToken, "$"),
+            (r#"{"a": 1,}"#, ParserErrKind::UnexpectedToken, "$"),
+            (
+                r#"{"a": 1 "b": 2}"#,
+                ParserErrKind::ExpectedToken(TokenKind::RCurlyBracket),
+                "$",
+            ),
+            (r#"{"a": [1, }"#, ParserErrKind::UnexpectedToken, "$.a[1]"),
+            (
+                r#"{"a": {"b" 1}}"#,
+                ParserErrKind::ExpectedToken(TokenKind::Colon),
+                "$.a",
+            ),
+            ("[}", ParserErrKind::UnexpectedToken, "$[0]"),
+            ("{1: 2}", ParserErrKind::UnexpectedToken, "$"),
+            ("[1, 01]", ParserErrKind::LeadingZero, "$[1]"),
+            // Strings are still checked, even though they aren't unescaped
+            (
+                r#"[{"a": "\x"}]"#,
+                ParserErrKind::InvalidEscapeSequence,
+                "$[0].a",
+            ),
+            (r#"["\uD800"]"#, ParserErrKind::LoneSurrogate, "$[0]"),
+            // Keys in the path are unescaped
+            (
+                r#"{"a\u0062": [1, tru]}"#,
+                ParserErrKind::UnrecognisedLiteral,
+                "$.ab[1]",
+            ),
+        ];
+
+        for (source, kind, path) in cases {
+            let err = Parser::parse::<Skipped>(source).map(|_| ()).unwrap_err();
+            assert_eq!(
+                (kind, path),
+                (err.kind, err.path.to_string().as_str()),
+                "{source}"
+            );
+        }
+
+        // The lexeme of a skipped string is still reported
+        let err = Parser::parse::<Skipped>(r#"{"a": 1 "b": 2}"#)
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(r#""b""#, err.lexeme);
+
+        let options = ParseOptions::new().max_depth(2);
+        let result = Parser::parse_with::<Skipped>("[[[]]]", &options).map(|_| ());
+        assert_eq!(
+            Err(ParserErrKind::DepthLimitExceeded),
+            result.map_err(|x| x.kind)
+        );
+    }
+
+    #[test]
+    fn test_skip_value_deeply_nested() {
+        // Nested too deeply to recurse through, but skipping doesn't need to
+        let source = format!("{}1{}", r#"[{"a": "#.repeat(100_000), "}]".repeat(100_000));
+        let options = ParseOptions::new().max_depth(usize::MAX);
+        assert!(Parser::parse_with::<Skipped>(&source, &options).is_ok());
+    }
 }
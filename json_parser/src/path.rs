@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::Serialiser;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+// Location of a value within the JSON, displayed like `$.history[3].success`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JsonPath(pub Vec<PathSegment>);
+
+impl JsonPath {
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+}
+
+// Keys that are not valid identifiers must use bracket notation, e.g. `$["first name"]`
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "$")?;
+
+        for segment in &self.0 {
+            match segment {
+                PathSegment::Key(key) if is_identifier(key) => write!(f, ".{key}")?,
+                PathSegment::Key(key) => write!(f, "[{}]", Serialiser::serialise(key))?,
+                PathSegment::Index(i) => write!(f, "[{i}]")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_root() {
+        assert_eq!("$", JsonPath::default().to_string());
+    }
+
+    #[test]
+    fn test_display() {
+        let path = JsonPath(vec![
+            PathSegment::Key("history".to_string()),
+            PathSegment::Index(3),
+            PathSegment::Key("success".to_string()),
+        ]);
+        assert_eq!("$.history[3].success", path.to_string());
+    }
+
+    #[test]
+    fn test_non_identifier_keys() {
+        let path = JsonPath(vec![
+            PathSegment::Key("first name".to_string()),
+            PathSegment::Key("3d".to_string()),
+            PathSegment::Key("a\"b".to_string()),
+            PathSegment::Key("".to_string()),
+            PathSegment::Key("_ok2".to_string()),
+        ]);
+        assert_eq!(
+            r#"$["first name"]["3d"]["a\"b"][""]._ok2"#,
+            path.to_string()
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use crate::{Serialise, Serialiser, json_value::JsonValue};
+
+// Tabs have no fixed width, so assume a common editor setting when measuring line width
+const TAB_WIDTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Indent {
+    Spaces(usize),
+    Tabs,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrettyOptions {
+    pub indent: Indent,
+    pub sort_keys: bool,
+    pub space_after_colon: bool,
+
+    // If set, arrays that fit within this many columns (including indentation) are written on one
+    // line. Otherwise every element goes on its own line
+    pub array_line_width: Option<usize>,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        Self {
+            indent: Indent::Spaces(2),
+            sort_keys: false,
+            space_after_colon: true,
+            array_line_width: None,
+        }
+    }
+}
+
+pub(crate) struct PrettyPrinter<'a> {
+    options: &'a PrettyOptions,
+    serialiser: Serialiser,
+}
+
+impl<'a> PrettyPrinter<'a> {
+    pub fn print(value: &JsonValue, options: &'a PrettyOptions) -> String {
+        let mut printer = PrettyPrinter {
+            options,
+            serialiser: Serialiser::default(),
+        };
+
+        printer.value(value, 0, 0);
+        printer.serialiser.into_string()
+    }
+
+    fn entries<'v>(
+        &self,
+        props: &'v HashMap<String, JsonValue>,
+    ) -> Vec<(&'v String, &'v JsonValue)> {
+        let mut entries: Vec<_> = props.iter().collect();
+        if self.options.sort_keys {
+            entries.sort_by_key(|(key, _)| *key);
+        }
+
+        entries
+    }
+
+    fn colon(&self) -> &'static str {
+        if self.options.space_after_colon {
+            ": "
+        } else {
+            ":"
+        }
+    }
+
+    fn indent_width(&self, depth: usize) -> usize {
+        match self.options.indent {
+            Indent::Spaces(n) => n * depth,
+            Indent::Tabs => TAB_WIDTH * depth,
+        }
+    }
+
+    fn newline(&mut self, depth: usize) {
+        self.serialiser.write_raw("\n");
+        let indent = match self.options.indent {
+            Indent::Spaces(n) => " ".repeat(n * depth),
+            Indent::Tabs => "\t".repeat(depth),
+        };
+        self.serialiser.write_raw(&indent);
+    }
+
+    // `column` is where the value starts on the current line, used to decide whether arrays fit
+    fn value(&mut self, value: &JsonValue, depth: usize, column: usize) {
+        match value {
+            JsonValue::Object(props) => self.object(props, depth),
+            JsonValue::Array(elems) => self.array(elems, depth, column),
+            _ => value.serialise(&mut self.serialiser),
+        }
+    }
+
+    fn object(&mut self, props: &HashMap<String, JsonValue>, depth: usize) {
+        if props.is_empty() {
+            self.serialiser.write_raw("{}");
+            return;
+        }
+
+        self.serialiser.write_raw("{");
+        for (i, (key, value)) in self.entries(props).into_iter().enumerate() {
+            if i > 0 {
+                self.serialiser.write_raw(",");
+            }
+            self.newline(depth + 1);
+
+            let mut key_serialiser = Serialiser::default();
+            key_serialiser.write_string(key);
+            let key = key_serialiser.into_string();
+
+            self.serialiser.write_raw(&key);
+            self.serialiser.write_raw(self.colon());
+
+            let column = self.indent_width(depth + 1) + key.chars().count() + self.colon().len();
+            self.value(value, depth + 1, column);
+        }
+
+        self.newline(depth);
+        self.serialiser.write_raw("}");
+    }
+
+    fn array(&mut self, elems: &[JsonValue], depth: usize, column: usize) {
+        if elems.is_empty() {
+            self.serialiser.write_raw("[]");
+            return;
+        }
+
+        // Put short arrays on one line if they fit
+        if let Some(width) = self.options.array_line_width {
+            let mut inline = Serialiser::default();
+            self.inline_array(elems, &mut inline);
+            let inline = inline.into_string();
+
+            if column + inline.chars().count() <= width {
+                self.serialiser.write_raw(&inline);
+                return;
+            }
+        }
+
+        self.serialiser.write_raw("[");
+        for (i, elem) in elems.iter().enumerate() {
+            if i > 0 {
+                self.serialiser.write_raw(",");
+            }
+            self.newline(depth + 1);
+            self.value(elem, depth + 1, self.indent_width(depth + 1));
+        }
+
+        self.newline(depth);
+        self.serialiser.write_raw("]");
+    }
+
+    // Single line version, with a space after each comma
+    fn inline_value(&self, value: &JsonValue, serialiser: &mut Serialiser) {
+        match value {
+            JsonValue::Object(props) => {
+                serialiser.write_raw("{");
+                for (i, (key, value)) in self.entries(props).into_iter().enumerate() {
+                    if i > 0 {
+                        serialiser.write_raw(", ");
+                    }
+
+                    serialiser.write_string(key);
+                    serialiser.write_raw(self.colon());
+                    self.inline_value(value, serialiser);
+                }
+                serialiser.write_raw("}");
+            }
+            JsonValue::Array(elems) => self.inline_array(elems, serialiser),
+            _ => value.serialise(serialiser),
+        }
+    }
+
+    fn inline_array(&self, elems: &[JsonValue], serialiser: &mut Serialiser) {
+        serialiser.write_raw("[");
+        for (i, elem) in elems.iter().enumerate() {
+            if i > 0 {
+                serialiser.write_raw(", ");
+            }
+
+            self.inline_value(elem, serialiser);
+        }
+        serialiser.write_raw("]");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parser;
+
+    use super::*;
+
+    fn parse(source: &str) -> JsonValue {
+        Parser::parse::<JsonValue>(source).unwrap()
+    }
+
+    #[test]
+    fn test_default() {
+        let value = parse(r#"{"name": "Jane Doe", "tags": ["a", "b"], "empty": {}, "none": []}"#);
+        let options = PrettyOptions {
+            sort_keys: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "{\n  \"empty\": {},\n  \"name\": \"Jane Doe\",\n  \"none\": [],\n  \"tags\": [\n    \"a\",\n    \"b\"\n  ]\n}",
+            PrettyPrinter::print(&value, &options)
+        );
+    }
+
+    #[test]
+    fn test_tabs_no_space() {
+        let value = parse(r#"{"nested": {"a": 1}}"#);
+        let options = PrettyOptions {
+            indent: Indent::Tabs,
+            space_after_colon: false,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "{\n\t\"nested\":{\n\t\t\"a\":1\n\t}\n}",
+            PrettyPrinter::print(&value, &options)
+        );
+    }
+
+    #[test]
+    fn test_indent_width() {
+        let value = parse("[1, [2]]");
+        let options = PrettyOptions {
+            indent: Indent::Spaces(4),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "[\n    1,\n    [\n        2\n    ]\n]",
+            PrettyPrinter::print(&value, &options)
+        );
+    }
+
+    #[test]
+    fn test_array_line_width() {
+        let value = parse(r#"{"short": [1, 2, 3], "long": ["aaaaaaaaaa", "bbbbbbbbbb"]}"#);
+        let options = PrettyOptions {
+            sort_keys: true,
+            array_line_width: Some(20),
+            ..Default::default()
+        };
+
+        // `  "short": [1, 2, 3]` is exactly 20 columns, the long array is not
+        assert_eq!(
+            "{\n  \"long\": [\n    \"aaaaaaaaaa\",\n    \"bbbbbbbbbb\"\n  ],\n  \"short\": [1, 2, 3]\n}",
+            PrettyPrinter::print(&value, &options)
+        );
+    }
+
+    #[test]
+    fn test_inline_nested() {
+        let value = parse(r#"[{"b": 2, "a": [1]}, null]"#);
+        let options = PrettyOptions {
+            sort_keys: true,
+            array_line_width: Some(80),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            r#"[{"a": [1], "b": 2}, null]"#,
+            PrettyPrinter::print(&value, &options)
+        );
+    }
+
+    #[test]
+    fn test_scalar() {
+        let value = JsonValue::String("line\nbreak".to_string());
+        assert_eq!(
+            r#""line\nbreak""#,
+            PrettyPrinter::print(&value, &PrettyOptions::default())
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::token::{Token, TokenKind};
+use crate::token::{Position, Span, Token, TokenKind};
 
 static BUG_END_OF_SOURCE: &str = "[BUG] Reached end of source when shouldn't be possible";
 static BUG_PREV_BEFORE_ADVANCE: &str = "[BUG] Called `prev` before advancing - no previous value";
@@ -8,86 +8,124 @@ pub struct ScannerErr {
     pub kind: ScannerErrKind,
     pub line: usize,
     pub lexeme: String,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ScannerErrKind {
     UnexpectedEndOfSource,
-    UnterminatedString,
     UnrecognisedSymbol,
     UnrecognisedLiteral,
-    InvalidNumber,
     InvalidEscapeSequence,
+    LoneSurrogate,
+    ControlCharacterInString(char),
+
+    // Number grammar violations
+    LeadingZero,
+    MissingIntegerDigits,
+    MissingFractionDigits,
+    MissingExponentDigits,
+    UnexpectedCharacterInNumber,
 }
 
 #[derive(Debug, Clone)]
 pub struct Scanner<'a> {
     source: &'a str,
-    token_start: usize,
-    current: usize,
-    line: usize,
+    token_start: Position,
+    current: Position,
+
+    // RFC 8259 forbids raw U+0000 to U+001F in strings, but some producers emit them anyway
+    allow_control_characters: bool,
+
+    // Strings are only checked, not unescaped, so their tokens have an empty value and lexeme
+    raw_strings: bool,
 }
 
 impl<'a> Scanner<'a> {
     pub fn init(source: &'a str) -> Self {
         Self {
             source,
-            current: 0,
-            token_start: 0,
-            line: 1,
+            current: Position::default(),
+            token_start: Position::default(),
+            allow_control_characters: false,
+            raw_strings: false,
+        }
+    }
+
+    pub fn allow_control_characters(&mut self, allow: bool) {
+        self.allow_control_characters = allow;
+    }
+
+    // Used when skipping values, where the contents of strings aren't needed
+    pub fn raw_strings(&mut self, raw: bool) {
+        self.raw_strings = raw;
+    }
+
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    fn span(&self) -> Span {
+        Span {
+            start: self.token_start,
+            end: self.current,
         }
     }
 
+    fn lexeme(&self) -> &'a str {
+        &self.source[self.token_start.offset..self.current.offset]
+    }
+
     fn make_token(&mut self, kind: TokenKind) -> Token {
-        let start = self.token_start;
-        Token::init(kind, self.line, &self.source[start..self.current])
+        Token::init(kind, self.span(), self.lexeme())
     }
 
     fn make_err(&self, kind: ScannerErrKind) -> ScannerErr {
         ScannerErr {
             kind,
-            line: self.line,
-            lexeme: self.source[self.token_start..self.current].to_string(),
+            line: self.token_start.line,
+            lexeme: self.lexeme().to_string(),
+            span: self.span(),
         }
     }
 
     fn advance(&mut self) -> Result<char, ScannerErr> {
         // When advancing, make sure to advance the correct number of bytes
-        // A character such as an emoji may be more than 1 byte, so increase `current` by the number
+        // A character such as an emoji may be more than 1 byte, so increase the offset by the number
         // of bytes of the char we advanced past
-        let remaining = &self.source[self.current..];
-        let mut chars = remaining.char_indices();
-        let (_, c) = chars
-            .next()
-            .ok_or(self.make_err(ScannerErrKind::UnexpectedEndOfSource))?;
-        let (next_byte_index, _) = chars.next().unwrap_or((remaining.len(), ' '));
+        let c = self.peek()?;
+        self.current.offset += c.len_utf8();
+
+        if c == '\n' {
+            self.current.line += 1;
+            self.current.column = 1;
+            self.current.column_utf16 = 1;
+        } else {
+            self.current.column += 1;
+            self.current.column_utf16 += c.len_utf16();
+        }
 
-        self.current += next_byte_index;
         Ok(c)
     }
 
     fn peek(&self) -> Result<char, ScannerErr> {
-        self.source[self.current..]
+        self.source[self.current.offset..]
             .chars()
             .next()
-            .ok_or(self.make_err(ScannerErrKind::UnexpectedEndOfSource))
+            .ok_or_else(|| self.make_err(ScannerErrKind::UnexpectedEndOfSource))
     }
 
     fn prev(&self) -> char {
-        self.source[self.current - 1..]
+        self.source[..self.current.offset]
             .chars()
-            .next()
+            .next_back()
             .expect(BUG_PREV_BEFORE_ADVANCE)
     }
 
     fn skip_whitespace(&mut self) {
         loop {
             match self.peek() {
-                Ok(' ' | '\t' | '\r') => {
-                    self.advance().expect(BUG_END_OF_SOURCE);
-                }
-                Ok('\n') => {
-                    self.line += 1;
+                Ok(' ' | '\t' | '\r' | '\n') => {
                     self.advance().expect(BUG_END_OF_SOURCE);
                 }
                 _ => {
@@ -98,7 +136,7 @@ impl<'a> Scanner<'a> {
     }
 
     fn is_at_end(&self) -> bool {
-        self.current >= self.source.len()
+        self.current.offset >= self.source.len()
     }
 
     fn matches(&mut self, c: char) -> bool {
@@ -123,60 +161,114 @@ impl<'a> Scanner<'a> {
         false
     }
 
-    fn number(&mut self) -> Result<Token, ScannerErr> {
-        // Consume digits - we already know we've got an initial one
+    // Consume as many digits as possible, returning how many there were
+    fn digits(&mut self) -> usize {
+        let mut count = 0;
         while matches!(self.peek(), Ok(c) if c.is_ascii_digit()) {
             self.advance().expect(BUG_END_OF_SOURCE);
+            count += 1;
         }
 
-        // If reach a `.`, include it and continue matching digits
-        // We know it is a float at this point
-        if self.matches('.') {
-            while matches!(self.peek(), Ok(c) if c.is_ascii_digit()) {
-                self.advance().expect(BUG_END_OF_SOURCE);
+        count
+    }
+
+    // Follows the RFC 8259 grammar: `-`? ( `0` | [1-9][0-9]* ) ( `.` [0-9]+ )? ( [eE] [-+]? [0-9]+ )?
+    fn number(&mut self) -> Result<Token, ScannerErr> {
+        // We have already consumed the first character, which is either `-` or a digit
+        // If it was a `-`, there must be a digit straight after it (e.g. not `-.5` or `--1`)
+        let mut first_digit = self.prev();
+        if first_digit == '-' {
+            match self.peek() {
+                Ok(c) if c.is_ascii_digit() => {
+                    first_digit = self.advance().expect(BUG_END_OF_SOURCE)
+                }
+                _ => return Err(self.make_err(ScannerErrKind::MissingIntegerDigits)),
             }
         }
 
-        let next_char = self.peek();
+        // A zero can only be followed by a fraction or exponent, not more digits (e.g. `0123`)
+        if first_digit == '0' {
+            if self.digits() > 0 {
+                return Err(self.make_err(ScannerErrKind::LeadingZero));
+            }
+        } else {
+            self.digits();
+        }
 
-        // Allow scientific notation e.g. 10e5
-        if let Ok(c) = next_char {
-            if c == 'e' || c == 'E' {
-                let mut has_number_after_e = false;
+        // If reach a `.`, there must be at least one digit after it (e.g. not `1.`)
+        if self.matches('.') && self.digits() == 0 {
+            return Err(self.make_err(ScannerErrKind::MissingFractionDigits));
+        }
 
-                self.advance().expect(BUG_END_OF_SOURCE);
+        // Allow scientific notation e.g. 10e5, with an optional sign before the digits
+        if self.matches_any(&['e', 'E']) {
+            self.matches_any(&['-', '+']);
 
-                // Consume `-` or `+` if it exists
-                self.matches_any(&['-', '+']);
+            if self.digits() == 0 {
+                return Err(self.make_err(ScannerErrKind::MissingExponentDigits));
+            }
+        }
 
-                while matches!(self.peek(), Ok(c) if c.is_ascii_digit()) {
-                    self.advance().expect(BUG_END_OF_SOURCE);
-                    has_number_after_e = true;
-                }
+        // The number must not run straight into something else, e.g. `1234a` or `1.2.3`
+        if matches!(self.peek(), Ok(c) if c.is_alphanumeric() || c == '.') {
+            self.advance().expect(BUG_END_OF_SOURCE);
+            return Err(self.make_err(ScannerErrKind::UnexpectedCharacterInNumber));
+        }
 
-                if !has_number_after_e {
-                    return Err(self.make_err(ScannerErrKind::InvalidNumber));
-                }
-            } else if c.is_alphabetic() {
-                return Err(self.make_err(ScannerErrKind::InvalidNumber));
+        Ok(self.make_token(TokenKind::Number))
+    }
+
+    // Read the 4 hex digits after a `\u`
+    fn hex_escape(&mut self) -> Result<u32, ScannerErr> {
+        // Read all 4 characters before reporting an error, so they are all in the lexeme
+        let mut value = 0;
+        let mut valid = true;
+        for _ in 0..4 {
+            match self.advance()?.to_digit(16) {
+                Some(digit) => value = value * 16 + digit,
+                None => valid = false,
             }
         }
 
-        // Check that we have at least one digit, not just a `-`
-        let lexeme = &self.source[self.token_start..self.current];
-        if lexeme == "-" {
-            return Err(self.make_err(ScannerErrKind::InvalidNumber));
+        if !valid {
+            return Err(self.make_err(ScannerErrKind::InvalidEscapeSequence));
         }
 
-        Ok(self.make_token(TokenKind::Number))
+        Ok(value)
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, ScannerErr> {
+        let code_unit = self.hex_escape()?;
+
+        // Characters outside the BMP are escaped as a UTF-16 surrogate pair, e.g. `\uD83D\uDE00`
+        // The high surrogate must be immediately followed by an escaped low surrogate
+        let code_point = match code_unit {
+            0xD800..=0xDBFF => {
+                if !(self.matches('\\') && self.matches('u')) {
+                    return Err(self.make_err(ScannerErrKind::LoneSurrogate));
+                }
+
+                let low = self.hex_escape()?;
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    return Err(self.make_err(ScannerErrKind::LoneSurrogate));
+                }
+
+                0x10000 + ((code_unit - 0xD800) << 10) + (low - 0xDC00)
+            }
+            0xDC00..=0xDFFF => return Err(self.make_err(ScannerErrKind::LoneSurrogate)),
+            _ => code_unit,
+        };
+
+        char::from_u32(code_point)
+            .ok_or_else(|| self.make_err(ScannerErrKind::InvalidEscapeSequence))
     }
 
     fn string(&mut self) -> Result<Token, ScannerErr> {
         let mut str_val = String::new();
         while self.peek()? != '"' {
             let chr = self.advance().expect(BUG_END_OF_SOURCE);
-            if chr == '\n' {
-                return Err(self.make_err(ScannerErrKind::UnterminatedString));
+            if chr < '\x20' && !self.allow_control_characters {
+                return Err(self.make_err(ScannerErrKind::ControlCharacterInString(chr)));
             }
 
             // Escape sequences
@@ -190,31 +282,27 @@ impl<'a> Scanner<'a> {
                     'r' => '\r',
                     't' => '\t',
                     '\\' => '\\',
-                    'u' => {
-                        // Unicode character - read next 4 hex values and parse
-                        let mut hex = String::with_capacity(4);
-                        for _ in 0..4 {
-                            hex.push(self.advance()?);
-                        }
-
-                        // Convert hex string to unicode char
-                        let digit = u32::from_str_radix(&hex, 16)
-                            .map_err(|_| self.make_err(ScannerErrKind::InvalidEscapeSequence))?;
-
-                        char::from_u32(digit)
-                            .ok_or(self.make_err(ScannerErrKind::InvalidEscapeSequence))?
-                    }
+                    'u' => self.unicode_escape()?,
                     _ => return Err(self.make_err(ScannerErrKind::InvalidEscapeSequence)),
                 };
 
-                str_val.push(value);
+                if !self.raw_strings {
+                    str_val.push(value);
+                }
                 continue;
             }
 
-            str_val.push(chr);
+            if !self.raw_strings {
+                str_val.push(chr);
+            }
         }
 
         self.advance().expect(BUG_END_OF_SOURCE);
+
+        // The span is enough to report errors at, so don't copy out the lexeme either
+        if self.raw_strings {
+            return Ok(Token::init(TokenKind::String(str_val), self.span(), ""));
+        }
         Ok(self.make_token(TokenKind::String(str_val)))
     }
 
@@ -225,7 +313,7 @@ impl<'a> Scanner<'a> {
         }
 
         // Check lexeme
-        let literal = &self.source[self.token_start..self.current];
+        let literal = self.lexeme();
         let kind = match literal {
             "null" => TokenKind::Null,
             "true" | "false" => TokenKind::Bool,
@@ -366,10 +454,9 @@ mod tests {
     #[test]
     fn test_invalid_tokens() {
         let cases = vec![
-            ("\"unterminated\n", ScannerErrKind::UnterminatedString),
             ("\"end of source", ScannerErrKind::UnexpectedEndOfSource),
-            ("1234e", ScannerErrKind::InvalidNumber),
-            ("1234a", ScannerErrKind::InvalidNumber),
+            ("1234e", ScannerErrKind::MissingExponentDigits),
+            ("1234a", ScannerErrKind::UnexpectedCharacterInNumber),
             ("notliteral", ScannerErrKind::UnrecognisedLiteral),
             ("_", ScannerErrKind::UnrecognisedSymbol),
             ("^", ScannerErrKind::UnrecognisedSymbol),
@@ -381,6 +468,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_valid_numbers() {
+        let cases = vec![
+            "0", "-0", "0.5", "-0.0", "0e5", "0E-5", "-0.0e+0", "10", "1.25", "9e99", "12.5E+3",
+        ];
+
+        for source in cases {
+            let mut scanner = Scanner::init(source);
+            let token = scanner.next_token();
+            assert!(
+                matches!(token, Ok(Some(Token { kind: TokenKind::Number, ref lexeme, .. })) if lexeme == source),
+                "Ensure the following number is valid: {source}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_numbers() {
+        let cases = vec![
+            ("0123", ScannerErrKind::LeadingZero),
+            ("-01", ScannerErrKind::LeadingZero),
+            ("00", ScannerErrKind::LeadingZero),
+            ("-", ScannerErrKind::MissingIntegerDigits),
+            ("-.5", ScannerErrKind::MissingIntegerDigits),
+            ("--1", ScannerErrKind::MissingIntegerDigits),
+            ("-a", ScannerErrKind::MissingIntegerDigits),
+            ("1.", ScannerErrKind::MissingFractionDigits),
+            ("1.e5", ScannerErrKind::MissingFractionDigits),
+            ("-0.]", ScannerErrKind::MissingFractionDigits),
+            ("1e", ScannerErrKind::MissingExponentDigits),
+            ("1e+", ScannerErrKind::MissingExponentDigits),
+            ("1.5E-", ScannerErrKind::MissingExponentDigits),
+            ("1e5a", ScannerErrKind::UnexpectedCharacterInNumber),
+            ("1.2.3", ScannerErrKind::UnexpectedCharacterInNumber),
+            ("0x10", ScannerErrKind::UnexpectedCharacterInNumber),
+        ];
+
+        for (source, expected) in cases {
+            let mut scanner = Scanner::init(source);
+            assert_eq!(
+                Err(expected),
+                scanner.next_token().map_err(|x| x.kind),
+                "Ensure the following number is invalid: {source}"
+            );
+        }
+    }
+
     #[test]
     fn test_valid_escape_sequences() {
         let cases = vec![
@@ -405,7 +539,7 @@ mod tests {
 
     #[test]
     fn test_invalid_escape_sequences() {
-        let cases = vec![r#""\uZZZZ""#, r#""\uD800""#, r#""bad\escape""#];
+        let cases = vec![r#""\uZZZZ""#, r#""\u+123""#, r#""bad\escape""#];
 
         for source in cases {
             let mut scanner = Scanner::init(source);
@@ -416,6 +550,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_surrogate_pairs() {
+        let cases = vec![
+            (r#""\uD83D\uDE00""#, "😀"),
+            (r#""\ud83d\ude00""#, "😀"),
+            (r#""a\uD834\uDD1Eb""#, "a𝄞b"),
+            (r#""\uDBFF\uDFFF""#, "\u{10FFFF}"),
+        ];
+
+        for (source, expected) in cases {
+            let mut scanner = Scanner::init(source);
+            assert_eq!(
+                Ok(Some(TokenKind::String(expected.to_string()))),
+                scanner.next_token().map(|x| x.map(|y| y.kind))
+            );
+        }
+    }
+
+    #[test]
+    fn test_lone_surrogates() {
+        let cases = vec![
+            r#""\uD800""#,
+            r#""\uD800 after""#,
+            r#""\uD800\n""#,
+            r#""\uD800\u0041""#,
+            r#""\uD800\uD800""#,
+            r#""\uDC00""#,
+            r#""\uDE00\uD83D""#,
+        ];
+
+        for source in cases {
+            let mut scanner = Scanner::init(source);
+            assert_eq!(
+                Err(ScannerErrKind::LoneSurrogate),
+                scanner.next_token().map_err(|x| x.kind),
+                "Ensure the following string is a lone surrogate: {source}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_control_characters() {
+        let cases = vec![
+            ("\"tab\there\"", '\t'),
+            ("\"unterminated\n", '\n'),
+            ("\"carriage\rreturn\"", '\r'),
+            ("\"nul\0\"", '\0'),
+            ("\"unit separator\x1F\"", '\x1F'),
+        ];
+
+        for (source, chr) in cases {
+            let mut scanner = Scanner::init(source);
+            assert_eq!(
+                Err(ScannerErrKind::ControlCharacterInString(chr)),
+                scanner.next_token().map_err(|x| x.kind)
+            );
+        }
+
+        // DEL and above are allowed
+        let mut scanner = Scanner::init("\"\x7F\"");
+        assert!(matches!(scanner.next_token(), Ok(Some(_))));
+    }
+
+    #[test]
+    fn test_allow_control_characters() {
+        let mut scanner = Scanner::init("\"tab\there\0\" \"multi\nline\" 3");
+        scanner.allow_control_characters(true);
+
+        let token = scanner.next_token().unwrap().unwrap();
+        assert_eq!(TokenKind::String("tab\there\0".to_string()), token.kind);
+
+        let token = scanner.next_token().unwrap().unwrap();
+        assert_eq!(TokenKind::String("multi\nline".to_string()), token.kind);
+
+        let token = scanner.next_token().unwrap().unwrap();
+        assert_eq!(2, token.line);
+    }
+
     #[test]
     fn test_line_numbers() {
         let source = "\"line 1\" \"still line 1\"\n2\n\r\n4\r\t4";
@@ -427,6 +639,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_spans() {
+        // `😀` is 4 bytes and 2 UTF-16 code units, `é` is 2 bytes and 1 UTF-16 code unit
+        let source = "[\"😀\", \"é\",\n  true]";
+        let expected = vec![
+            ((1, 1, 1, 0), (1, 2, 2, 1)),
+            ((1, 2, 2, 1), (1, 5, 6, 7)),
+            ((1, 5, 6, 7), (1, 6, 7, 8)),
+            ((1, 7, 8, 9), (1, 10, 11, 13)),
+            ((1, 10, 11, 13), (1, 11, 12, 14)),
+            ((2, 3, 3, 17), (2, 7, 7, 21)),
+            ((2, 7, 7, 21), (2, 8, 8, 22)),
+        ];
+        let mut scanner = Scanner::init(source);
+
+        let position = |(line, column, column_utf16, offset)| Position {
+            line,
+            column,
+            column_utf16,
+            offset,
+        };
+
+        for (start, end) in expected {
+            let span = scanner.next_token().unwrap().unwrap().span;
+            assert_eq!(position(start), span.start);
+            assert_eq!(position(end), span.end);
+        }
+    }
+
+    #[test]
+    fn test_error_span() {
+        let mut scanner = Scanner::init("[1, 2, 1.2.3]");
+        for _ in 0..5 {
+            scanner.next_token().unwrap();
+        }
+
+        let err = scanner.next_token().unwrap_err();
+        assert_eq!(Span::single_line(7, 11), err.span);
+        assert_eq!("1.2.", err.lexeme);
+    }
+
+    #[test]
+    fn test_non_ascii_symbol() {
+        let mut scanner = Scanner::init("€");
+        assert_eq!(
+            Err(ScannerErrKind::UnrecognisedSymbol),
+            scanner.next_token().map_err(|x| x.kind)
+        );
+    }
+
+    #[test]
+    fn test_raw_strings() {
+        let mut scanner = Scanner::init(r#""a\n\u00e9" "\q""#);
+        scanner.raw_strings(true);
+
+        let token = scanner.next_token().unwrap().unwrap();
+        assert_eq!(TokenKind::String(String::new()), token.kind);
+        assert_eq!("", token.lexeme);
+        assert_eq!(Span::single_line(0, 11), token.span);
+
+        // Escapes are still checked
+        let err = scanner.next_token().unwrap_err();
+        assert_eq!(ScannerErrKind::InvalidEscapeSequence, err.kind);
+    }
+
     #[test]
     fn test_lexemes() {
         let source = "\"lexeme 1\" \"lexeme 2\" 3 4 true";
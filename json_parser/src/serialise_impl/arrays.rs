@@ -0,0 +1,47 @@
+use crate::{Serialise, Serialiser};
+
+impl<T: Serialise> Serialise for Vec<T> {
+    fn serialise(&self, serialiser: &mut Serialiser) {
+        serialiser.write_raw("[");
+
+        for (i, elem) in self.iter().enumerate() {
+            if i > 0 {
+                serialiser.write_raw(",");
+            }
+
+            elem.serialise(serialiser);
+        }
+
+        serialiser.write_raw("]");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json_value::JsonValue;
+
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let result = Serialiser::serialise(&Vec::<u32>::new());
+        assert_eq!("[]", result);
+    }
+
+    #[test]
+    fn test_nested() {
+        let result = Serialiser::serialise(&vec![vec![1, 2], vec![3, 4, 5]]);
+        assert_eq!("[[1,2],[3,4,5]]", result);
+    }
+
+    #[test]
+    fn test_mixed() {
+        let result = Serialiser::serialise(&vec![
+            JsonValue::String("first".to_string()),
+            JsonValue::Number(3.0),
+            JsonValue::Bool(true),
+            JsonValue::Null,
+        ]);
+        assert_eq!(r#"["first",3,true,null]"#, result);
+    }
+}
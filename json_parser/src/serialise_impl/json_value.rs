@@ -0,0 +1,66 @@
+use crate::{Serialise, Serialiser, json_value::JsonValue};
+
+impl Serialise for JsonValue {
+    fn serialise(&self, serialiser: &mut Serialiser) {
+        match self {
+            Self::Object(props) => props.serialise(serialiser),
+            Self::Array(elems) => elems.serialise(serialiser),
+            Self::String(val) => val.serialise(serialiser),
+            Self::Number(val) => val.serialise(serialiser),
+            Self::Bool(val) => val.serialise(serialiser),
+            Self::Null => serialiser.write_raw("null"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_object() {
+        let result = Serialiser::serialise(&JsonValue::Object(HashMap::from([(
+            "prop".to_string(),
+            JsonValue::Number(3.0),
+        )])));
+        assert_eq!(r#"{"prop":3}"#, result);
+    }
+
+    #[test]
+    fn test_array() {
+        let result = Serialiser::serialise(&JsonValue::Array(vec![
+            JsonValue::Number(1.0),
+            JsonValue::Number(2.5),
+            JsonValue::Number(-3e-7),
+        ]));
+        assert_eq!("[1,2.5,-3e-7]", result);
+    }
+
+    #[test]
+    fn test_primitives() {
+        let cases = vec![
+            (JsonValue::String("hi".to_string()), r#""hi""#),
+            (JsonValue::Number(5.55), "5.55"),
+            (JsonValue::Bool(false), "false"),
+            (JsonValue::Null, "null"),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(expected, Serialiser::serialise(&value));
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let source =
+            r#"{"name": "Jane Doe", "nested": {"age": 32, "tags": ["a", "b"]}, "none": null}"#;
+        let value = Parser::parse::<JsonValue>(source).unwrap();
+
+        let result = Parser::parse::<JsonValue>(&Serialiser::serialise(&value));
+        assert_eq!(Ok(value), result);
+    }
+}
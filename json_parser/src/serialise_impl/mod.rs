@@ -0,0 +1,6 @@
+pub mod arrays;
+pub mod json_value;
+pub mod objects;
+pub mod options;
+pub mod primitives;
+pub mod strings;
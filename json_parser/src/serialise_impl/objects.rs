@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use crate::{Serialise, Serialiser};
+
+impl<T: Serialise> Serialise for HashMap<String, T> {
+    fn serialise(&self, serialiser: &mut Serialiser) {
+        serialiser.write_raw("{");
+
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 {
+                serialiser.write_raw(",");
+            }
+
+            serialiser.write_string(key);
+            serialiser.write_raw(":");
+            value.serialise(serialiser);
+        }
+
+        serialiser.write_raw("}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Parser, json_value::JsonValue};
+
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let result = Serialiser::serialise(&HashMap::<String, u32>::new());
+        assert_eq!("{}", result);
+    }
+
+    #[test]
+    fn test_single_prop() {
+        let result = Serialiser::serialise(&HashMap::from([("prop".to_string(), 5)]));
+        assert_eq!(r#"{"prop":5}"#, result);
+    }
+
+    #[test]
+    fn test_escaped_key() {
+        let result = Serialiser::serialise(&HashMap::from([("a \"key\"".to_string(), true)]));
+        assert_eq!(r#"{"a \"key\"":true}"#, result);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // Key order is not guaranteed, so check by parsing the output again
+        let value = HashMap::from([
+            ("prop1".to_string(), JsonValue::Number(5.0)),
+            ("prop2".to_string(), JsonValue::Bool(true)),
+            ("prop3".to_string(), JsonValue::String("test".to_string())),
+        ]);
+
+        let result = Parser::parse::<HashMap<String, JsonValue>>(&Serialiser::serialise(&value));
+        assert_eq!(Ok(value), result);
+    }
+}
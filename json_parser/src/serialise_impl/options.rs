@@ -0,0 +1,33 @@
+use crate::{Serialise, Serialiser};
+
+impl<T: Serialise> Serialise for Option<T> {
+    fn serialise(&self, serialiser: &mut Serialiser) {
+        match self {
+            Some(value) => value.serialise(serialiser),
+            None => serialiser.write_raw("null"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_none() {
+        let result = Serialiser::serialise(&None::<u32>);
+        assert_eq!("null", result);
+    }
+
+    #[test]
+    fn test_with_value() {
+        let result = Serialiser::serialise(&Some("42".to_string()));
+        assert_eq!(r#""42""#, result);
+    }
+
+    #[test]
+    fn test_nested_with_value() {
+        let result = Serialiser::serialise(&Some(Some(true)));
+        assert_eq!("true", result);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{Serialise, Serialiser, parse_impl::primitives::JsonNumber, serialiser::SerialiseErr};
+
+impl<T: JsonNumber> Serialise for T {
+    fn serialise(&self, serialiser: &mut Serialiser) {
+        if self.is_finite() {
+            serialiser.write_raw(&self.to_json());
+        } else {
+            // Writing null instead would silently change the value, so refuse to write it
+            serialiser.fail(SerialiseErr::NonFiniteFloat(self.to_string()));
+        }
+    }
+}
+
+impl Serialise for bool {
+    fn serialise(&self, serialiser: &mut Serialiser) {
+        serialiser.write_raw(if *self { "true" } else { "false" });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_signed_int() {
+        let result = Serialiser::serialise(&-5i64);
+        assert_eq!("-5", result);
+    }
+
+    #[test]
+    fn test_unsigned_int() {
+        let result = Serialiser::serialise(&u128::MAX);
+        assert_eq!(u128::MAX.to_string(), result);
+    }
+
+    #[test]
+    fn test_float() {
+        let result = Serialiser::serialise(&-5.1f32);
+        assert_eq!("-5.1", result);
+
+        let result = Serialiser::serialise(&3.0f64);
+        assert_eq!("3", result);
+    }
+
+    #[test]
+    fn test_float_shortest() {
+        let cases = vec![
+            (1e300, "1e300"),
+            (-2.5e-300, "-2.5e-300"),
+            (0.1 + 0.2, "0.30000000000000004"),
+            (1e15, "1000000000000000"),
+            (f64::MAX, "1.7976931348623157e308"),
+        ];
+
+        for (value, expected) in cases {
+            let result = Serialiser::serialise(&value);
+            assert_eq!(expected, result);
+            assert_eq!(value, Parser::parse::<f64>(&result).unwrap());
+        }
+
+        let result = Serialiser::serialise(&1e30f32);
+        assert_eq!("1e30", result);
+    }
+
+    #[test]
+    fn test_non_finite_float() {
+        let result = Serialiser::try_serialise(&f64::NAN);
+        assert_eq!(Err(SerialiseErr::NonFiniteFloat("NaN".to_string())), result);
+
+        let result = Serialiser::try_serialise(&vec![1.0, f32::NEG_INFINITY]);
+        assert_eq!(
+            Err(SerialiseErr::NonFiniteFloat("-inf".to_string())),
+            result
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot serialise `inf`")]
+    fn test_non_finite_float_panics() {
+        Serialiser::serialise(&f64::INFINITY);
+    }
+
+    #[test]
+    fn test_bool() {
+        let result = Serialiser::serialise(&true);
+        assert_eq!("true", result);
+
+        let result = Serialiser::serialise(&false);
+        assert_eq!("false", result);
+    }
+}
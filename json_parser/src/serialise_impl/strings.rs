@@ -0,0 +1,52 @@
+use crate::{Serialise, Serialiser};
+
+impl Serialise for String {
+    fn serialise(&self, serialiser: &mut Serialiser) {
+        serialiser.write_string(self);
+    }
+}
+
+impl Serialise for str {
+    fn serialise(&self, serialiser: &mut Serialiser) {
+        serialiser.write_string(self);
+    }
+}
+
+// So structs can borrow their strings
+impl Serialise for &str {
+    fn serialise(&self, serialiser: &mut Serialiser) {
+        serialiser.write_string(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_quoted() {
+        let result = Serialiser::serialise(&"test".to_string());
+        assert_eq!(r#""test""#, result);
+    }
+
+    #[test]
+    fn test_borrowed() {
+        let result = Serialiser::serialise("test");
+        assert_eq!(r#""test""#, result);
+
+        let result = Serialiser::serialise(&vec!["a", "b"]);
+        assert_eq!(r#"["a","b"]"#, result);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cases = vec!["©", "\n", "\r", "\x08", "/", "\\", "\"", "\x01", "😀"];
+
+        for value in cases {
+            let result = Parser::parse::<String>(&Serialiser::serialise(&value.to_string()));
+            assert_eq!(Ok(value.to_string()), result);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::{error::Error, fmt};
+
+pub trait Serialise {
+    fn serialise(&self, serialiser: &mut Serialiser);
+}
+
+// Types written as properties of an enclosing object, for `#[json(flatten)]`
+// Each property is preceded by a comma unless `*first` is set, which is then cleared
+pub trait SerialiseFields {
+    fn serialise_fields(&self, serialiser: &mut Serialiser, first: &mut bool);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SerialiseErr {
+    // NaN or infinity, formatted as Rust would
+    NonFiniteFloat(String),
+}
+
+impl fmt::Display for SerialiseErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerialiseErr::NonFiniteFloat(value) => {
+                write!(
+                    f,
+                    "cannot serialise `{value}`, as JSON has no representation for it"
+                )
+            }
+        }
+    }
+}
+
+impl Error for SerialiseErr {}
+
+#[derive(Debug, Clone, Default)]
+pub struct Serialiser {
+    output: String,
+    error: Option<SerialiseErr>,
+}
+
+impl Serialiser {
+    // Panics if the value cannot be represented in JSON - use `try_serialise` to get an error instead
+    pub fn serialise<T: Serialise + ?Sized>(value: &T) -> String {
+        Self::try_serialise(value).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn try_serialise<T: Serialise + ?Sized>(value: &T) -> Result<String, SerialiseErr> {
+        let mut serialiser = Serialiser::default();
+        value.serialise(&mut serialiser);
+
+        serialiser.finish()
+    }
+
+    // Panics if an error was reported, like `serialise`
+    pub fn into_string(self) -> String {
+        self.finish().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn finish(self) -> Result<String, SerialiseErr> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.output),
+        }
+    }
+
+    // Report that a value cannot be written. `Serialise` impls have no way of stopping early, so
+    // the first error is kept and returned once serialisation finishes
+    pub fn fail(&mut self, err: SerialiseErr) {
+        self.error.get_or_insert(err);
+    }
+
+    // Write JSON directly to the output - the caller is responsible for making sure it is valid
+    pub fn write_raw(&mut self, json: &str) {
+        self.output.push_str(json);
+    }
+
+    // Write a quoted string, escaping any characters that cannot appear in a JSON string as-is
+    pub fn write_string(&mut self, value: &str) {
+        self.output.push('"');
+
+        for chr in value.chars() {
+            match chr {
+                '"' => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\x08' => self.output.push_str("\\b"),
+                '\x0C' => self.output.push_str("\\f"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                // Any other control character must use a unicode escape
+                c if c < '\x20' => self.output.push_str(&format!("\\u{:04X}", c as u32)),
+                c => self.output.push(c),
+            }
+        }
+
+        self.output.push('"');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_string() {
+        let cases = vec![
+            ("plain", r#""plain""#),
+            ("quote \" here", r#""quote \" here""#),
+            ("back\\slash", r#""back\\slash""#),
+            ("new\nline\ttab\r", r#""new\nline\ttab\r""#),
+            ("\x08\x0C", r#""\b\f""#),
+            ("\x00\x1F", r#""\u0000\u001F""#),
+            ("/ stays as-is", r#""/ stays as-is""#),
+            ("unicode 😀 ©", r#""unicode 😀 ©""#),
+        ];
+
+        for (value, expected) in cases {
+            let mut serialiser = Serialiser::default();
+            serialiser.write_string(value);
+            assert_eq!(expected, serialiser.output);
+        }
+    }
+
+    #[test]
+    fn test_write_raw() {
+        let mut serialiser = Serialiser::default();
+        serialiser.write_raw("[");
+        serialiser.write_raw("1");
+        serialiser.write_raw("]");
+        assert_eq!("[1]", serialiser.output);
+    }
+}
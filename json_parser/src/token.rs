@@ -1,16 +1,66 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    // Lines and columns start at 1, as shown in editors
+    pub line: usize,
+    pub column: usize,
+    // Column counted in UTF-16 code units, as used by JavaScript and LSP based tooling
+    pub column_utf16: usize,
+
+    // Bytes from the start of the source, starting at 0
+    pub offset: usize,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self {
+            line: 1,
+            column: 1,
+            column_utf16: 1,
+            offset: 0,
+        }
+    }
+}
+
+// The end position is exclusive - it is the position of the first character after the span
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[cfg(test)]
+impl Span {
+    // Shorthand for tests where the source is a single line of ASCII
+    pub fn single_line(start: usize, end: usize) -> Self {
+        let position = |offset| Position {
+            line: 1,
+            column: offset + 1,
+            column_utf16: offset + 1,
+            offset,
+        };
+
+        Self {
+            start: position(start),
+            end: position(end),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Token {
     pub kind: TokenKind,
     pub line: usize,
     pub lexeme: String,
+    pub span: Span,
 }
 
 impl Token {
-    pub fn init(kind: TokenKind, line: usize, lexeme: &str) -> Self {
+    pub fn init(kind: TokenKind, span: Span, lexeme: &str) -> Self {
         Self {
             kind,
-            line,
+            line: span.start.line,
             lexeme: lexeme.to_string(),
+            span,
         }
     }
 }
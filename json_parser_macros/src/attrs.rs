@@ -0,0 +1,253 @@
+use syn::{
+    Attribute, Error, ExprPath, LitStr, Path, Result, Token, WherePredicate, meta::ParseNestedMeta,
+    punctuated::Punctuated,
+};
+
+use crate::case::RenameRule;
+
+// How the variant of an enum is stored in the JSON
+pub enum Tagging {
+    // `{"Variant": data}`, or `"Variant"` for unit variants
+    External,
+    // `{"tag": "Variant", ...data}`, from `#[json(tag = "...")]`
+    Internal { tag: String },
+    // `{"tag": "Variant", "content": data}`, from `#[json(tag = "...", content = "...")]`
+    Adjacent { tag: String, content: String },
+    // Just the data, from `#[json(untagged)]`
+    Untagged,
+}
+
+// Overrides `ParseOptions::unknown_fields` for a struct or enum
+#[derive(Clone, Copy)]
+pub enum UnknownFields {
+    // `#[json(deny_unknown_fields)]`
+    Deny,
+    // `#[json(ignore_unknown_fields)]`
+    Ignore,
+}
+
+// What the container attributes are on, since some only make sense for some of them
+#[derive(Clone, Copy, PartialEq)]
+pub enum Container {
+    // A struct with named fields
+    Struct,
+    // A tuple, newtype or unit struct, which isn't parsed from an object
+    TupleStruct,
+    Enum,
+}
+
+// Options set on a struct or enum with `#[json(...)]`
+#[derive(Default)]
+pub struct ContainerAttrs {
+    // Kept as literals so that invalid combinations can be reported at them
+    pub tag: Option<LitStr>,
+    pub content: Option<LitStr>,
+    pub untagged: bool,
+    // Convention for the names of all fields (of a struct) or variants (of an enum)
+    pub rename_all: Option<RenameRule>,
+    // Fill in missing fields from the struct's `Default` implementation, from `#[json(default)]`
+    pub default: bool,
+    pub unknown_fields: Option<UnknownFields>,
+    // Replaces the bounds on type parameters added to the generated impls, from
+    // `#[json(bound = "T: Trait")]`
+    pub bound: Option<Vec<WherePredicate>>,
+    // Path to the `json_parser` crate, if it isn't `::json_parser`, from `#[json(crate = "...")]`
+    pub crate_path: Option<Path>,
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute], container: Container) -> Result<Self> {
+        let mut result = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("json")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    check_container(&meta, container, &[Container::Enum])?;
+                    result.tag = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("content") {
+                    check_container(&meta, container, &[Container::Enum])?;
+                    result.content = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("untagged") {
+                    check_container(&meta, container, &[Container::Enum])?;
+                    result.untagged = true;
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    check_container(&meta, container, &[Container::Struct])?;
+                    result.default = true;
+                    Ok(())
+                } else if meta.path.is_ident("deny_unknown_fields") {
+                    check_container(&meta, container, &[Container::Struct, Container::Enum])?;
+                    result.set_unknown_fields(&meta, UnknownFields::Deny)
+                } else if meta.path.is_ident("ignore_unknown_fields") {
+                    check_container(&meta, container, &[Container::Struct, Container::Enum])?;
+                    result.set_unknown_fields(&meta, UnknownFields::Ignore)
+                } else if meta.path.is_ident("rename_all") {
+                    check_container(&meta, container, &[Container::Struct, Container::Enum])?;
+                    let rule = meta.value()?.parse::<LitStr>()?;
+                    result.rename_all = Some(RenameRule::parse(&rule)?);
+                    Ok(())
+                } else if meta.path.is_ident("crate") {
+                    result.crate_path = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("bound") {
+                    let predicates = meta
+                        .value()?
+                        .parse::<LitStr>()?
+                        .parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?;
+                    result.bound = Some(predicates.into_iter().collect());
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown json container attribute"))
+                }
+            })?;
+        }
+
+        result.check_tagging()?;
+        Ok(result)
+    }
+
+    fn set_unknown_fields(
+        &mut self,
+        meta: &ParseNestedMeta,
+        unknown_fields: UnknownFields,
+    ) -> Result<()> {
+        if self.unknown_fields.is_some() {
+            return Err(meta.error(
+                "only one of `deny_unknown_fields` and `ignore_unknown_fields` can be used",
+            ));
+        }
+        self.unknown_fields = Some(unknown_fields);
+        Ok(())
+    }
+
+    fn check_tagging(&self) -> Result<()> {
+        match (&self.tag, &self.content, self.untagged) {
+            (Some(tag), Some(content), _) if tag.value() == content.value() => Err(Error::new(
+                content.span(),
+                "`tag` and `content` must be different",
+            )),
+            (None, Some(content), _) => Err(Error::new(
+                content.span(),
+                "`content` can only be used along with `tag`",
+            )),
+            (Some(tag), _, true) => Err(Error::new(
+                tag.span(),
+                "`untagged` cannot be used along with `tag`",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    // The combination has already been checked by `parse`
+    pub fn tagging(&self) -> Tagging {
+        match (&self.tag, &self.content) {
+            (Some(tag), Some(content)) => Tagging::Adjacent {
+                tag: tag.value(),
+                content: content.value(),
+            },
+            (Some(tag), None) => Tagging::Internal { tag: tag.value() },
+            _ if self.untagged => Tagging::Untagged,
+            _ => Tagging::External,
+        }
+    }
+}
+
+// Give an error at the attribute if it can't be used on `container`, rather than ignoring it
+fn check_container(
+    meta: &ParseNestedMeta,
+    container: Container,
+    allowed: &[Container],
+) -> Result<()> {
+    if allowed.contains(&container) {
+        return Ok(());
+    }
+
+    let message = match allowed {
+        [Container::Enum] => "can only be used on enums",
+        [Container::Struct] => "can only be used on structs with named fields",
+        _ => "cannot be used on tuple or unit structs",
+    };
+    let name = meta.path.get_ident().map(ToString::to_string);
+    Err(meta.error(format!("`{}` {message}", name.unwrap_or_default())))
+}
+
+// Options set on an enum variant with `#[json(...)]`
+#[derive(Default)]
+pub struct VariantAttrs {
+    // Name used in the JSON instead of the variant name, from `#[json(rename = "...")]`
+    pub rename: Option<String>,
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut result = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("json")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    result.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown json variant attribute"))
+                }
+            })?;
+        }
+
+        Ok(result)
+    }
+}
+
+// Where the value of a field comes from if its property is missing
+pub enum DefaultValue {
+    // `#[json(default)]`
+    Trait,
+    // `#[json(default = "path::to::fn")]`
+    Function(ExprPath),
+}
+
+// Options set on a field with `#[json(...)]`
+#[derive(Default)]
+pub struct FieldAttrs {
+    // Name used in the JSON instead of the field name, from `#[json(rename = "...")]`
+    pub rename: Option<String>,
+    pub default: Option<DefaultValue>,
+    // Collect every property that isn't a field into this map, from `#[json(extra)]`
+    pub extra: bool,
+    // Parse the fields of this field's struct from the parent object, from `#[json(flatten)]`
+    pub flatten: bool,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut result = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("json")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    result.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    result.default = if meta.input.peek(Token![=]) {
+                        let path = meta.value()?.parse::<LitStr>()?.parse::<ExprPath>()?;
+                        Some(DefaultValue::Function(path))
+                    } else {
+                        Some(DefaultValue::Trait)
+                    };
+                    Ok(())
+                } else if meta.path.is_ident("extra") {
+                    result.extra = true;
+                    Ok(())
+                } else if meta.path.is_ident("flatten") {
+                    result.flatten = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown json field attribute"))
+                }
+            })?;
+        }
+
+        Ok(result)
+    }
+}
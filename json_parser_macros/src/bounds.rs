@@ -0,0 +1,26 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Generics, WherePredicate, parse_quote};
+
+// Generics for an implementation of `bound` (such as `Parse`) for the type. Each type parameter
+// has to implement `bound` as well, unless the predicates are given with `#[json(bound = "...")]`
+pub fn with_bounds(
+    generics: &Generics,
+    custom: Option<&[WherePredicate]>,
+    bound: TokenStream2,
+) -> Generics {
+    let mut generics = generics.clone();
+
+    let predicates: Vec<WherePredicate> = match custom {
+        Some(predicates) => predicates.to_vec(),
+        None => generics
+            .type_params()
+            .map(|param| {
+                let ident = &param.ident;
+                parse_quote! { #ident: #bound }
+            })
+            .collect(),
+    };
+
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
@@ -0,0 +1,252 @@
+use syn::{Error, LitStr, Result};
+
+// Naming convention to convert field and variant names to, from `#[json(rename_all = "...")]`
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+static RULES: &[(&str, RenameRule)] = &[
+    ("lowercase", RenameRule::Lower),
+    ("UPPERCASE", RenameRule::Upper),
+    ("PascalCase", RenameRule::Pascal),
+    ("camelCase", RenameRule::Camel),
+    ("snake_case", RenameRule::Snake),
+    ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+    ("kebab-case", RenameRule::Kebab),
+    ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+];
+
+// Split a snake_case field or PascalCase variant name into its words
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_lowercase = false;
+
+    for (i, &c) in chars.iter().enumerate() {
+        // The last capital of an acronym starts the next word, e.g. `HTTPServer`
+        let acronym_end = i > 0
+            && chars[i - 1].is_uppercase()
+            && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+        let boundary = c == '_' || (c.is_uppercase() && (prev_lowercase || acronym_end));
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        if c != '_' {
+            word.push(c);
+        }
+        prev_lowercase = c.is_lowercase() || c.is_ascii_digit();
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn capitalise(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn join(words: &[String], separator: &str, convert: fn(&str) -> String) -> String {
+    words
+        .iter()
+        .map(|word| convert(word))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+impl RenameRule {
+    pub fn parse(rule: &LitStr) -> Result<Self> {
+        match RULES.iter().find(|(name, _)| *name == rule.value()) {
+            Some((_, rule)) => Ok(*rule),
+            None => {
+                let names: Vec<_> = RULES
+                    .iter()
+                    .map(|(name, _)| format!("\"{name}\""))
+                    .collect();
+                Err(Error::new(
+                    rule.span(),
+                    format!(
+                        "unknown rename rule `{}`, expected one of {}",
+                        rule.value(),
+                        names.join(", ")
+                    ),
+                ))
+            }
+        }
+    }
+
+    pub fn apply(self, name: &str) -> String {
+        // Leading and trailing underscores aren't part of any word, so are kept as they are
+        let trimmed = name.trim_start_matches('_');
+        let (prefix, trimmed) = name.split_at(name.len() - trimmed.len());
+        let (trimmed, suffix) = trimmed.split_at(trimmed.trim_end_matches('_').len());
+        let words = words(trimmed);
+
+        let converted = match self {
+            RenameRule::Lower => trimmed.to_lowercase(),
+            RenameRule::Upper => trimmed.to_uppercase(),
+            RenameRule::Pascal => join(&words, "", capitalise),
+            // The whole of the first word is lowercase, so acronyms don't keep their capitals
+            RenameRule::Camel => match words.split_first() {
+                Some((first, rest)) => first.to_lowercase() + &join(rest, "", capitalise),
+                None => String::new(),
+            },
+            RenameRule::Snake => join(&words, "_", str::to_lowercase),
+            RenameRule::ScreamingSnake => join(&words, "_", str::to_uppercase),
+            RenameRule::Kebab => join(&words, "-", str::to_lowercase),
+            RenameRule::ScreamingKebab => join(&words, "-", str::to_uppercase),
+        };
+
+        format!("{prefix}{converted}{suffix}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    use super::*;
+
+    fn rule(name: &str) -> RenameRule {
+        RenameRule::parse(&LitStr::new(name, Span::call_site())).unwrap()
+    }
+
+    #[test]
+    fn test_words() {
+        let cases = vec![
+            ("user_name", vec!["user", "name"]),
+            ("UserName", vec!["User", "Name"]),
+            ("HTTPServer", vec!["HTTP", "Server"]),
+            ("parseJSON", vec!["parse", "JSON"]),
+            ("field2Name", vec!["field2", "Name"]),
+            ("field_2", vec!["field", "2"]),
+            ("IPv4", vec!["I", "Pv4"]),
+            ("a__b", vec!["a", "b"]),
+            ("ID", vec!["ID"]),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(expected, words(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let names = [
+            "user_name",
+            "HTTPServer",
+            "field2Name",
+            "_private",
+            "trailing_",
+        ];
+        let cases = vec![
+            (
+                "lowercase",
+                [
+                    "user_name",
+                    "httpserver",
+                    "field2name",
+                    "_private",
+                    "trailing_",
+                ],
+            ),
+            (
+                "UPPERCASE",
+                [
+                    "USER_NAME",
+                    "HTTPSERVER",
+                    "FIELD2NAME",
+                    "_PRIVATE",
+                    "TRAILING_",
+                ],
+            ),
+            (
+                "PascalCase",
+                [
+                    "UserName",
+                    "HTTPServer",
+                    "Field2Name",
+                    "_Private",
+                    "Trailing_",
+                ],
+            ),
+            (
+                "camelCase",
+                [
+                    "userName",
+                    "httpServer",
+                    "field2Name",
+                    "_private",
+                    "trailing_",
+                ],
+            ),
+            (
+                "snake_case",
+                [
+                    "user_name",
+                    "http_server",
+                    "field2_name",
+                    "_private",
+                    "trailing_",
+                ],
+            ),
+            (
+                "SCREAMING_SNAKE_CASE",
+                [
+                    "USER_NAME",
+                    "HTTP_SERVER",
+                    "FIELD2_NAME",
+                    "_PRIVATE",
+                    "TRAILING_",
+                ],
+            ),
+            (
+                "kebab-case",
+                [
+                    "user-name",
+                    "http-server",
+                    "field2-name",
+                    "_private",
+                    "trailing_",
+                ],
+            ),
+            (
+                "SCREAMING-KEBAB-CASE",
+                [
+                    "USER-NAME",
+                    "HTTP-SERVER",
+                    "FIELD2-NAME",
+                    "_PRIVATE",
+                    "TRAILING_",
+                ],
+            ),
+        ];
+
+        for (rule_name, expected) in cases {
+            for (name, expected) in names.iter().zip(expected) {
+                assert_eq!(expected, rule(rule_name).apply(name), "{rule_name} {name}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_rule() {
+        let err = RenameRule::parse(&LitStr::new("Title Case", Span::call_site()));
+        assert!(err.is_err());
+    }
+}
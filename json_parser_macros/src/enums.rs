@@ -0,0 +1,565 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{DataEnum, DeriveInput, Error, Fields, Ident, Result, ext::IdentExt};
+
+use crate::{
+    attrs::{Container, ContainerAttrs, Tagging, UnknownFields, VariantAttrs},
+    bounds::with_bounds,
+    case::RenameRule,
+    structs::{
+        Field, ObjectOptions, check_unnamed_fields, fields, parse_object, parse_tuple, parse_unit,
+        unknown_property, write_properties, write_tuple,
+    },
+    wrap_impls,
+};
+
+struct Variant {
+    ident: Ident,
+    // Name used for the variant in the JSON
+    json_name: String,
+    fields: Fields,
+    // Named fields of a struct variant
+    named_fields: Vec<Field>,
+}
+
+fn variants(data: &DataEnum, rename_all: Option<RenameRule>) -> Result<Vec<Variant>> {
+    data.variants
+        .iter()
+        .map(|variant| {
+            let attrs = VariantAttrs::parse(&variant.attrs)?;
+            // Same as fields, the `r#` of a raw identifier isn't part of the name
+            let name = variant.ident.unraw().to_string();
+            let json_name = match (attrs.rename, rename_all) {
+                (Some(rename), _) => rename,
+                (None, Some(rule)) => rule.apply(&name),
+                (None, None) => name,
+            };
+
+            Ok(Variant {
+                ident: variant.ident.clone(),
+                json_name,
+                fields: variant.fields.clone(),
+                named_fields: match variant.fields {
+                    Fields::Named(_) => fields(&variant.fields, None)?,
+                    _ => {
+                        check_unnamed_fields(&variant.fields)?;
+                        Vec::new()
+                    }
+                },
+            })
+        })
+        .collect()
+}
+
+// Generate an expression which parses the data of a variant on its own, giving `Self`
+// Unit variants are `null`, newtypes are their value, tuples are arrays and structs are objects
+fn parse_data(
+    enum_name: &Ident,
+    variant: &Variant,
+    unknown_fields: Option<UnknownFields>,
+) -> TokenStream2 {
+    let ident = &variant.ident;
+
+    match &variant.fields {
+        Fields::Unit => parse_unit(quote! { Self::#ident }),
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let ty = &fields.unnamed[0].ty;
+            quote! { Self::#ident(<#ty as _json_parser::Parse>::parse(parser)?) }
+        }
+        Fields::Unnamed(fields) => parse_tuple(enum_name, quote! { Self::#ident }, fields),
+        Fields::Named(_) => parse_object(
+            enum_name,
+            quote! { Self::#ident },
+            &variant.named_fields,
+            &ObjectOptions {
+                unknown_fields,
+                ..Default::default()
+            },
+        ),
+    }
+}
+
+// `"Variant"` for unit variants, otherwise `{"Variant": data}`. Unit variants are also accepted
+// as `{"Variant": null}`, which some other serialisers write
+fn parse_external(
+    enum_name: &Ident,
+    variants: &[Variant],
+    unknown_fields: Option<UnknownFields>,
+) -> TokenStream2 {
+    let unit_variants: Vec<_> = variants
+        .iter()
+        .filter(|variant| matches!(variant.fields, Fields::Unit))
+        .collect();
+
+    // Only allow strings if there are unit variants, otherwise they are the wrong type
+    let unit_arm = if unit_variants.is_empty() {
+        quote! {}
+    } else {
+        let idents = unit_variants.iter().map(|variant| &variant.ident);
+        let json_names: Vec<_> = unit_variants
+            .iter()
+            .map(|variant| &variant.json_name)
+            .collect();
+
+        quote! {
+            _json_parser::TokenKind::String(ref value) => {
+                // Listed in the error if the string doesn't match any of them
+                const VARIANTS: &[&str] = &[#(#json_names),*];
+
+                match value.as_str() {
+                    #( #json_names => ::core::result::Result::Ok(Self::#idents), )*
+                    _ => ::core::result::Result::Err(parser.make_err_from_token(
+                        _json_parser::ParserErrKind::UnknownVariant {
+                            found: value.clone(),
+                            expected: VARIANTS,
+                        },
+                        &token,
+                    )),
+                }
+            }
+        }
+    };
+
+    // Likewise for objects
+    let data_arm = if variants.is_empty() {
+        quote! {}
+    } else {
+        let json_names: Vec<_> = variants.iter().map(|variant| &variant.json_name).collect();
+        let data_parsers = variants
+            .iter()
+            .map(|variant| parse_data(enum_name, variant, unknown_fields));
+
+        quote! {
+            _json_parser::TokenKind::LCurlyBracket => {
+                const VARIANTS: &[&str] = &[#(#json_names),*];
+
+                parser.enter_nested()?;
+
+                // The object has a single property, with the variant as its key
+                let key_token = parser.advance()?;
+                let key = match key_token.kind {
+                    _json_parser::TokenKind::String(ref key) => key.clone(),
+                    _ => {
+                        return ::core::result::Result::Err(
+                            parser.make_err_prev(_json_parser::ParserErrKind::UnexpectedToken),
+                        );
+                    }
+                };
+                parser.consume(_json_parser::TokenKind::Colon)?;
+
+                parser.push_path(_json_parser::PathSegment::Key(key.clone()));
+                let value = match key.as_str() {
+                    #( #json_names => #data_parsers, )*
+                    _ => return ::core::result::Result::Err(parser.make_err_from_token(
+                        _json_parser::ParserErrKind::UnknownVariant {
+                            found: key,
+                            expected: VARIANTS,
+                        },
+                        &key_token,
+                    )),
+                };
+                parser.pop_path();
+
+                parser.consume(_json_parser::TokenKind::RCurlyBracket)?;
+                parser.exit_nested();
+
+                ::core::result::Result::Ok(value)
+            }
+        }
+    };
+
+    quote! {
+        let token = parser.advance()?;
+        match token.kind {
+            #unit_arm
+            #data_arm
+            _ => ::core::result::Result::Err(
+                parser.make_type_err(::core::stringify!(#enum_name), &token),
+            ),
+        }
+    }
+}
+
+// `{"tag": "Variant", ...fields}`, which only works for unit and struct variants
+// The data of a tuple variant isn't an object, so there is nowhere to put the tag alongside it
+fn check_internal_variants(variants: &[Variant]) -> Result<()> {
+    match variants
+        .iter()
+        .find(|variant| matches!(variant.fields, Fields::Unnamed(_)))
+    {
+        Some(variant) => Err(Error::new_spanned(
+            &variant.ident,
+            "internally tagged enums can only have unit and struct variants",
+        )),
+        None => Ok(()),
+    }
+}
+
+fn parse_internal(
+    enum_name: &Ident,
+    variants: &[Variant],
+    tag: &str,
+    unknown_fields: Option<UnknownFields>,
+) -> Result<TokenStream2> {
+    check_internal_variants(variants)?;
+
+    let json_names: Vec<_> = variants.iter().map(|variant| &variant.json_name).collect();
+    let mut object_parsers = Vec::new();
+    for variant in variants {
+        let ident = &variant.ident;
+        object_parsers.push(parse_object(
+            enum_name,
+            quote! { Self::#ident },
+            &variant.named_fields,
+            &ObjectOptions {
+                skip_key: Some(tag),
+                unknown_fields,
+                ..Default::default()
+            },
+        ));
+    }
+
+    Ok(quote! {
+        const VARIANTS: &[&str] = &[#(#json_names),*];
+
+        // The tag could be anywhere in the object, so find it before parsing the rest
+        let variant = parser.find_tag(#tag, VARIANTS, ::core::stringify!(#enum_name))?;
+        ::core::result::Result::Ok(match variant.as_str() {
+            #( #json_names => #object_parsers, )*
+            _ => ::core::unreachable!(),
+        })
+    })
+}
+
+// `{"tag": "Variant", "content": data}`, where unit variants can leave out the content
+fn parse_adjacent(
+    enum_name: &Ident,
+    variants: &[Variant],
+    tag: &str,
+    content: &str,
+    unknown_fields: Option<UnknownFields>,
+) -> TokenStream2 {
+    let json_names: Vec<_> = variants.iter().map(|variant| &variant.json_name).collect();
+    let data_parsers = variants
+        .iter()
+        .map(|variant| parse_data(enum_name, variant, unknown_fields));
+
+    let unit_variants: Vec<_> = variants
+        .iter()
+        .filter(|variant| matches!(variant.fields, Fields::Unit))
+        .collect();
+    let unit_idents = unit_variants.iter().map(|variant| &variant.ident);
+    let unknown_property = unknown_property(unknown_fields);
+    let unit_json_names = unit_variants.iter().map(|variant| &variant.json_name);
+
+    quote! {
+        const VARIANTS: &[&str] = &[#(#json_names),*];
+
+        // The tag could come after the content, so find it before parsing the rest
+        let variant = parser.find_tag(#tag, VARIANTS, ::core::stringify!(#enum_name))?;
+
+        let l_curly_token = parser.advance()?;
+        parser.enter_nested()?;
+        parser.start_object();
+
+        let mut had_comma = false;
+        let mut value = ::core::option::Option::None;
+
+        while !parser.check(_json_parser::TokenKind::RCurlyBracket)? {
+            let token = parser.advance()?;
+            let key = match token.kind {
+                _json_parser::TokenKind::String(ref key) => key.clone(),
+                _ => {
+                    return ::core::result::Result::Err(
+                        parser.make_err_prev(_json_parser::ParserErrKind::UnexpectedToken),
+                    );
+                }
+            };
+            parser.consume(_json_parser::TokenKind::Colon)?;
+
+            parser.push_path(_json_parser::PathSegment::Key(key.clone()));
+            if parser.check_key(&token)? {
+                match key.as_str() {
+                    // Already known to be one of the variants
+                    #tag => {
+                        <::std::string::String as _json_parser::Parse>::parse(parser)?;
+                    }
+                    #content => {
+                        value = ::core::option::Option::Some(match variant.as_str() {
+                            #( #json_names => #data_parsers, )*
+                            _ => ::core::unreachable!(),
+                        });
+                    }
+                    _ => { #unknown_property }
+                }
+            } else {
+                // Repeated key whose earlier value is kept
+                parser.skip_value()?;
+            }
+            parser.pop_path();
+
+            // Once no comma at end, we have reached end of object
+            had_comma = parser.check(_json_parser::TokenKind::Comma)?;
+            if had_comma {
+                parser.advance()?;
+            } else {
+                break;
+            }
+        }
+
+        // No trailing comma, unless the options allow it
+        if had_comma && !parser.options().allow_trailing_commas {
+            return ::core::result::Result::Err(
+                parser.make_err_prev(_json_parser::ParserErrKind::UnexpectedToken),
+            );
+        }
+
+        parser.consume(_json_parser::TokenKind::RCurlyBracket)?;
+        parser.end_object();
+        parser.exit_nested();
+
+        match value {
+            ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+            ::core::option::Option::None => match variant.as_str() {
+                #( #unit_json_names => ::core::result::Result::Ok(Self::#unit_idents), )*
+                _ => ::core::result::Result::Err(
+                    parser.make_missing_err(&[#content], &l_curly_token),
+                ),
+            },
+        }
+    }
+}
+
+// Just the data, trying each variant in order until one of them parses
+fn parse_untagged(
+    enum_name: &Ident,
+    variants: &[Variant],
+    unknown_fields: Option<UnknownFields>,
+) -> TokenStream2 {
+    let data_parsers = variants
+        .iter()
+        .map(|variant| parse_data(enum_name, variant, unknown_fields));
+
+    quote! {
+        let checkpoint = parser.checkpoint();
+        let mut errors = ::std::vec::Vec::new();
+
+        #(
+            let parse_variant = |
+                parser: &mut _json_parser::Parser,
+            | -> ::core::result::Result<Self, _json_parser::ParserErr> {
+                ::core::result::Result::Ok(#data_parsers)
+            };
+            match parse_variant(parser) {
+                ::core::result::Result::Ok(value) => return ::core::result::Result::Ok(value),
+                // No other variant could get past it either
+                ::core::result::Result::Err(err) if err.kind.is_fatal() => {
+                    return ::core::result::Result::Err(err);
+                }
+                ::core::result::Result::Err(err) => errors.push(err),
+            }
+            parser.restore(&checkpoint);
+        )*
+
+        ::core::result::Result::Err(
+            parser.make_no_match_err(errors, ::core::stringify!(#enum_name)),
+        )
+    }
+}
+
+pub fn derive_json_deserialise_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
+    let enum_name = &input.ident;
+    let attrs = ContainerAttrs::parse(&input.attrs, Container::Enum)?;
+    let variants = variants(data, attrs.rename_all)?;
+    let unknown_fields = attrs.unknown_fields;
+
+    let parse_body = match attrs.tagging() {
+        Tagging::External => parse_external(enum_name, &variants, unknown_fields),
+        Tagging::Internal { tag } => parse_internal(enum_name, &variants, &tag, unknown_fields)?,
+        Tagging::Adjacent { tag, content } => {
+            parse_adjacent(enum_name, &variants, &tag, &content, unknown_fields)
+        }
+        Tagging::Untagged => parse_untagged(enum_name, &variants, unknown_fields),
+    };
+
+    let generics = with_bounds(
+        &input.generics,
+        attrs.bound.as_deref(),
+        quote! { _json_parser::Parse },
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Generated impl block
+    let generated_impl = quote! {
+        impl #impl_generics _json_parser::Parse for #enum_name #ty_generics #where_clause {
+            fn parse(
+                parser: &mut _json_parser::Parser,
+            ) -> ::core::result::Result<Self, _json_parser::ParserErr> {
+                #parse_body
+            }
+        }
+    };
+
+    Ok(wrap_impls(attrs.crate_path.as_ref(), generated_impl))
+}
+
+// Generate the pattern matching a variant, binding each of its fields
+fn variant_pattern(variant: &Variant) -> TokenStream2 {
+    let ident = &variant.ident;
+
+    match &variant.fields {
+        Fields::Unit => quote! { Self::#ident },
+        Fields::Unnamed(fields) => {
+            let names = (0..fields.unnamed.len()).map(|i| format_ident!("element_{i}"));
+            quote! { Self::#ident(#(#names),*) }
+        }
+        Fields::Named(fields) => {
+            let names = fields.named.iter().filter_map(|field| field.ident.as_ref());
+            let bindings = names.clone().map(field_binding);
+            quote! { Self::#ident { #(#names: #bindings),* } }
+        }
+    }
+}
+
+// Fields are bound to generated names, so they can't shadow the variables of the generated code,
+// such as `serialiser`
+fn field_binding(name: &Ident) -> Ident {
+    format_ident!("__field_{}", name.unraw())
+}
+
+// Generate code writing the data of a variant on its own, the reverse of `parse_data`
+fn write_data(variant: &Variant) -> TokenStream2 {
+    match &variant.fields {
+        Fields::Unit => quote! { serialiser.write_raw("null"); },
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            quote! { _json_parser::Serialise::serialise(element_0, serialiser); }
+        }
+        Fields::Unnamed(fields) => write_tuple((0..fields.unnamed.len()).map(|i| {
+            let name = format_ident!("element_{i}");
+            quote! { #name }
+        })),
+        Fields::Named(_) => {
+            let properties = write_properties(&variant.named_fields, |name| {
+                let binding = field_binding(name);
+                quote! { #binding }
+            });
+            quote! {
+                serialiser.write_raw("{");
+                #[allow(unused_variables)]
+                let __first = &mut true;
+                #properties
+                serialiser.write_raw("}");
+            }
+        }
+    }
+}
+
+fn write_variant(variant: &Variant, tagging: &Tagging) -> TokenStream2 {
+    let json_name = &variant.json_name;
+    let is_unit = matches!(variant.fields, Fields::Unit);
+
+    match tagging {
+        Tagging::External if is_unit => quote! { serialiser.write_string(#json_name); },
+        Tagging::External => {
+            let data = write_data(variant);
+            quote! {
+                serialiser.write_raw("{");
+                serialiser.write_string(#json_name);
+                serialiser.write_raw(":");
+                #data
+                serialiser.write_raw("}");
+            }
+        }
+        Tagging::Internal { tag } => {
+            let properties = if is_unit {
+                quote! {}
+            } else {
+                let properties = write_properties(&variant.named_fields, |name| {
+                    let binding = field_binding(name);
+                    quote! { #binding }
+                });
+                quote! {
+                    #[allow(unused_variables)]
+                    let __first = &mut false;
+                    #properties
+                }
+            };
+
+            quote! {
+                serialiser.write_raw("{");
+                serialiser.write_string(#tag);
+                serialiser.write_raw(":");
+                serialiser.write_string(#json_name);
+                #properties
+                serialiser.write_raw("}");
+            }
+        }
+        Tagging::Adjacent { tag, content } => {
+            let content = if is_unit {
+                quote! {}
+            } else {
+                let data = write_data(variant);
+                quote! {
+                    serialiser.write_raw(",");
+                    serialiser.write_string(#content);
+                    serialiser.write_raw(":");
+                    #data
+                }
+            };
+
+            quote! {
+                serialiser.write_raw("{");
+                serialiser.write_string(#tag);
+                serialiser.write_raw(":");
+                serialiser.write_string(#json_name);
+                #content
+                serialiser.write_raw("}");
+            }
+        }
+        Tagging::Untagged => write_data(variant),
+    }
+}
+
+pub fn derive_json_serialise_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
+    let enum_name = &input.ident;
+    let attrs = ContainerAttrs::parse(&input.attrs, Container::Enum)?;
+    let variants = variants(data, attrs.rename_all)?;
+    let tagging = attrs.tagging();
+    if let Tagging::Internal { .. } = tagging {
+        check_internal_variants(&variants)?;
+    }
+
+    let patterns = variants.iter().map(variant_pattern);
+    let writers = variants
+        .iter()
+        .map(|variant| write_variant(variant, &tagging));
+
+    // An empty enum can't be constructed, but still needs to match on `*self` for that to be known
+    let match_body = if variants.is_empty() {
+        quote! { match *self {} }
+    } else {
+        quote! {
+            match self {
+                #( #patterns => { #writers } )*
+            }
+        }
+    };
+
+    let generics = with_bounds(
+        &input.generics,
+        attrs.bound.as_deref(),
+        quote! { _json_parser::Serialise },
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Generated impl block
+    let generated_impl = quote! {
+        impl #impl_generics _json_parser::Serialise for #enum_name #ty_generics #where_clause {
+            fn serialise(&self, serialiser: &mut _json_parser::Serialiser) {
+                #match_body
+            }
+        }
+    };
+
+    Ok(wrap_impls(attrs.crate_path.as_ref(), generated_impl))
+}
@@ -1,122 +1,63 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{Data, DataStruct, DeriveInput, Fields, Ident};
+use syn::{Data, DeriveInput, Error, Path, parse_macro_input};
 
-fn derive_json_deserialise_struct(struct_name: &Ident, data: &DataStruct) -> TokenStream {
-    let fields = match &data.fields {
-        Fields::Named(data) => data,
-        _ => panic!(
-            "JSON deserialising can only be derived for named field structs (no tuple or unit structs)"
-        ),
-    };
-
-    // Code generation
-    // fields_struct is a temporary object to store the field data when it's being parsed
-    // Each value is initialised to None, and set once it is found
-    let mut fields_struct_types = Vec::new();
-    let mut fields_struct_init = Vec::new();
-
-    // When we come across a property, set the value in the fields_struct
-    // If the value does not exist in the fields_struct, report an error
-    let mut field_setters = Vec::new();
-
-    // Initialise the user's struct with the data collected
-    // If there is a field missing, report an error
-    let mut struct_init_lines = Vec::new();
-
-    // Loop through each field
-    for field in &fields.named {
-        let name = field.ident.as_ref().unwrap();
-        let ty = &field.ty;
-
-        // Generated code
-        let field_type = quote! { #name: Option<#ty> };
-        let field_init = quote! { #name: None };
-        let field_setter =
-            quote! { stringify!(#name) => parsed_fields.#name = Some(<#ty>::parse(parser)?), };
-        let struct_init_line = quote! {
-            #name: parsed_fields.#name.ok_or(
-                parser.make_err_from_token(ParserErrKind::MissingProperty(stringify!(#name).to_string()), &l_curly_token)
-            )?
-        };
-
-        // Add to vecs
-        fields_struct_types.push(field_type);
-        fields_struct_init.push(field_init);
-        field_setters.push(field_setter);
-        struct_init_lines.push(struct_init_line);
-    }
-
-    // Generated impl block
-    let generated_impl = quote! {
-        impl Parse for #struct_name {
-            fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
-                let l_curly_token = parser.consume(TokenKind::LCurlyBracket)?;
-
-                let mut had_comma = false;
-
-                // Temporary object to store field data. Initialise all values to None
-                let mut parsed_fields = {
-                    struct ParsedFields {
-                        #( #fields_struct_types, )*
-                    }
+mod attrs;
+mod bounds;
+mod case;
+mod enums;
+mod structs;
 
-                    ParsedFields {
-                        #( #fields_struct_init, )*
-                    }
-                };
-
-                // Loop through all properties, until reaching closing bracket
-                while !parser.check(TokenKind::RCurlyBracket)? {
-                    let token = parser.advance()?;
-                    match token.kind {
-                        TokenKind::String(ref key) => {
-                            parser.consume(TokenKind::Colon)?;
-
-                            // Assign the data to the parsed_fields struct
-                            match key.as_str() {
-                                #(#field_setters)*
-                                _ => return Err(parser.make_err_from_token(ParserErrKind::UnknownProperty, &token)),
-                            };
-
-                            // Once no comma at end, we have reached end of object
-                            had_comma = parser.check(TokenKind::Comma)?;
-                            if had_comma {
-                                parser.advance()?;
-                            } else {
-                                break;
-                            }
-                        }
-                        _ => return Err(parser.make_err_prev(ParserErrKind::UnexpectedToken)),
-                    }
-                }
-
-                // No trailing comma
-                if had_comma {
-                    return Err(parser.make_err_prev(ParserErrKind::UnexpectedToken));
-                }
+#[proc_macro_derive(JsonDeserialise, attributes(json))]
+pub fn derive_json_deserialise(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let result = match &input.data {
+        Data::Struct(data) => structs::derive_json_deserialise_struct(&input, data),
+        Data::Enum(data) => enums::derive_json_deserialise_enum(&input, data),
+        Data::Union(data) => Err(Error::new_spanned(
+            data.union_token,
+            "Cannot derive JsonDeserialise on a union",
+        )),
+    };
 
-                parser.consume(TokenKind::RCurlyBracket)?;
+    // Errors are reported as compile errors at the item or attribute they are about
+    result.unwrap_or_else(Error::into_compile_error).into()
+}
 
-                // Convert parsed_fields into the user's struct
-                // If data is missing, return an error
-                return Ok(#struct_name {
-                    #(#struct_init_lines),*
-                });
-            }
-        }
+#[proc_macro_derive(JsonSerialise, attributes(json))]
+pub fn derive_json_serialise(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let result = match &input.data {
+        Data::Struct(data) => structs::derive_json_serialise_struct(&input, data),
+        Data::Enum(data) => enums::derive_json_serialise_enum(&input, data),
+        Data::Union(data) => Err(Error::new_spanned(
+            data.union_token,
+            "Cannot derive JsonSerialise on a union",
+        )),
     };
 
-    generated_impl.into()
+    // Errors are reported as compile errors at the item or attribute they are about
+    result.unwrap_or_else(Error::into_compile_error).into()
 }
 
-#[proc_macro_derive(JsonDeserialise)]
-pub fn derive_json_deserialise(input: TokenStream) -> TokenStream {
-    let input: DeriveInput = syn::parse(input).unwrap();
+// Generated code refers to everything by its full path, with `_json_parser` for the crate (or
+// `#[json(crate = "...")]`), so it works without any imports and can't clash with the caller's
+// names. The impls go in an anonymous const so the alias isn't added to the caller's module
+fn wrap_impls(crate_path: Option<&Path>, impls: TokenStream2) -> TokenStream2 {
+    let crate_path = match crate_path {
+        Some(path) => quote! { #path },
+        None => quote! { ::json_parser },
+    };
 
-    match &input.data {
-        Data::Struct(data) => derive_json_deserialise_struct(&input.ident, data),
-        _ => panic!("Cannot derive JsonDeserialise on this type"),
+    quote! {
+        const _: () = {
+            use #crate_path as _json_parser;
+
+            #impls
+        };
     }
 }
@@ -0,0 +1,741 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    DataStruct, DeriveInput, Error, Fields, FieldsUnnamed, Ident, Index, Result, Type,
+    ext::IdentExt,
+};
+
+use crate::{
+    attrs::{Container, ContainerAttrs, DefaultValue, FieldAttrs, UnknownFields},
+    bounds::with_bounds,
+    case::RenameRule,
+    wrap_impls,
+};
+
+pub struct Field {
+    ident: Ident,
+    ty: Type,
+    // Name used for the field in the JSON
+    json_name: String,
+    default: Option<DefaultValue>,
+    extra: bool,
+    flatten: bool,
+}
+
+// `Option` fields can be left out of the JSON. This can only go by the name of the type, so
+// doesn't work if `Option` has been aliased
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+// Collect the named fields of a struct or struct variant, along with their attributes
+// Tuple and unit structs aren't parsed from objects, so some container attributes don't apply
+fn container(data: &DataStruct) -> Container {
+    match data.fields {
+        Fields::Named(_) => Container::Struct,
+        _ => Container::TupleStruct,
+    }
+}
+
+// Fields without names aren't properties, so none of the field attributes apply to them
+pub fn check_unnamed_fields(fields: &Fields) -> Result<()> {
+    let Fields::Unnamed(fields) = fields else {
+        return Ok(());
+    };
+
+    let json_attrs = fields.unnamed.iter().flat_map(|field| &field.attrs);
+    match json_attrs
+        .into_iter()
+        .find(|attr| attr.path().is_ident("json"))
+    {
+        Some(attr) => Err(Error::new_spanned(
+            attr,
+            "json field attributes can only be used on named fields",
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn fields(fields: &Fields, rename_all: Option<RenameRule>) -> Result<Vec<Field>> {
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().unwrap();
+            let attrs = FieldAttrs::parse(&field.attrs)?;
+            // Raw identifiers such as `r#type` are named without the `r#`
+            let name = ident.unraw().to_string();
+            let json_name = match (attrs.rename, rename_all) {
+                (Some(rename), _) => rename,
+                (None, Some(rule)) => rule.apply(&name),
+                (None, None) => name,
+            };
+
+            Ok(Field {
+                ident,
+                ty: field.ty.clone(),
+                json_name,
+                default: attrs.default,
+                extra: attrs.extra,
+                flatten: attrs.flatten,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some(field) = fields.iter().filter(|field| field.extra).nth(1) {
+        return Err(Error::new_spanned(
+            &field.ident,
+            "only one field can have `#[json(extra)]`",
+        ));
+    }
+    if let Some(field) = fields.iter().find(|field| field.extra && field.flatten) {
+        return Err(Error::new_spanned(
+            &field.ident,
+            "a field can't have both `#[json(extra)]` and `#[json(flatten)]`",
+        ));
+    }
+
+    Ok(fields)
+}
+
+// How `parse_object` handles properties which aren't fields, and fields which aren't properties
+#[derive(Default)]
+pub struct ObjectOptions<'a> {
+    // Enums which store their tag inside the object skip it, so it isn't unknown
+    pub skip_key: Option<&'a str>,
+    // Fill in missing fields from `Self::default()`
+    pub container_default: bool,
+    // If not set, `ParseOptions::unknown_fields` is used
+    pub unknown_fields: Option<UnknownFields>,
+}
+
+// Generate the code run when a property doesn't match anything, for the property's `token`
+pub fn unknown_property(unknown_fields: Option<UnknownFields>) -> TokenStream2 {
+    let deny = quote! {
+        return ::core::result::Result::Err(
+            parser.make_err_from_token(_json_parser::ParserErrKind::UnknownProperty, &token),
+        )
+    };
+
+    match unknown_fields {
+        Some(UnknownFields::Deny) => deny,
+        Some(UnknownFields::Ignore) => quote! { parser.skip_value()? },
+        None => quote! {
+            if parser.options().unknown_fields == _json_parser::UnknownFields::Ignore {
+                parser.skip_value()?;
+            } else {
+                #deny;
+            }
+        },
+    }
+}
+
+// Generate the type holding the values of the fields while they are being parsed. This is a
+// tuple with an element for each field, since the type can't be named in `ParseFields::Fields`
+fn parsed_fields_type(fields: &[Field]) -> TokenStream2 {
+    let types = fields.iter().map(|field| {
+        let ty = &field.ty;
+        if field.extra {
+            quote! { #ty }
+        } else if field.flatten {
+            quote! { <#ty as _json_parser::ParseFields>::Fields }
+        } else {
+            quote! { ::core::option::Option<#ty> }
+        }
+    });
+
+    quote! { (#(#types,)*) }
+}
+
+// Generate the initial value of `parsed_fields_type`, before any fields have been found
+fn parsed_fields_init(fields: &[Field]) -> TokenStream2 {
+    let inits = fields.iter().map(|field| {
+        let ty = &field.ty;
+        if field.extra {
+            quote! { ::core::default::Default::default() }
+        } else if field.flatten {
+            quote! { <#ty as _json_parser::ParseFields>::empty_fields() }
+        } else {
+            quote! { ::core::option::Option::None }
+        }
+    });
+
+    quote! { (#(#inits,)*) }
+}
+
+// Generate an expression which parses the value of the property `key` into `parsed_fields`, giving
+// true if it is a field. Keys which aren't a field of this struct are tried on each flattened
+// struct in turn, and then put in the extra field
+fn parse_field(fields: &[Field]) -> TokenStream2 {
+    let mut field_setters = Vec::new();
+    let mut fallbacks = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let index = Index::from(i);
+        let ty = &field.ty;
+        let json_name = &field.json_name;
+
+        if field.flatten {
+            fallbacks.push(quote! {
+                <#ty as _json_parser::ParseFields>::parse_field(
+                    &mut parsed_fields.#index,
+                    key,
+                    parser,
+                )?
+            });
+        } else if !field.extra {
+            field_setters.push(quote! {
+                #json_name => {
+                    let value = <#ty as _json_parser::Parse>::parse(parser)?;
+                    parsed_fields.#index = ::core::option::Option::Some(value);
+                    true
+                }
+            });
+        }
+    }
+
+    if let Some(i) = fields.iter().position(|field| field.extra) {
+        let index = Index::from(i);
+        fallbacks.push(quote! {
+            {
+                parsed_fields.#index.insert(key.to_string(), _json_parser::Parse::parse(parser)?);
+                true
+            }
+        });
+    }
+
+    // Parenthesised, since a block as a match arm body would end the arm before `||`
+    let fallback = match fallbacks.len() {
+        0 => quote! { false },
+        1 => quote! { #(#fallbacks)* },
+        _ => quote! { (#(#fallbacks)||*) },
+    };
+
+    quote! {
+        match key {
+            #(#field_setters)*
+            _ => #fallback,
+        }
+    }
+}
+
+// Generate an expression which converts `parsed_fields` into `Some(constructor { fields })`,
+// using the default of any missing fields. If required fields are missing, their names are added
+// to `missing` (a `&mut Vec<&str>` in scope) and it gives `None`, so every missing field in the
+// object can be reported at once, including those of flattened structs
+fn finish_fields(
+    constructor: &TokenStream2,
+    fields: &[Field],
+    container_default: bool,
+) -> TokenStream2 {
+    let mut value_lines = Vec::new();
+    let mut required = Vec::new();
+    let mut struct_init_lines = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let index = Index::from(i);
+        let name = &field.ident;
+        let ty = &field.ty;
+        let json_name = &field.json_name;
+        let value = format_ident!("field_{i}");
+
+        let missing_value = match &field.default {
+            Some(DefaultValue::Trait) => Some(quote! { ::core::default::Default::default() }),
+            Some(DefaultValue::Function(path)) => Some(quote! { #path() }),
+            None if container_default => Some(quote! { default_value.#name }),
+            None if is_option(ty) => Some(quote! { ::core::option::Option::None }),
+            None => None,
+        };
+
+        value_lines.push(if field.extra {
+            quote! { let #value = parsed_fields.#index; }
+        } else if field.flatten {
+            let finish = quote! {
+                <#ty as _json_parser::ParseFields>::finish_fields(parsed_fields.#index, missing)
+            };
+            if let Some(missing_value) = missing_value {
+                // The flattened struct's missing fields aren't reported if it has a default
+                quote! {
+                    let missing_len = missing.len();
+                    let #value = match #finish {
+                        ::core::option::Option::Some(value) => value,
+                        ::core::option::Option::None => {
+                            missing.truncate(missing_len);
+                            #missing_value
+                        }
+                    };
+                }
+            } else {
+                required.push(value.clone());
+                quote! { let #value = #finish; }
+            }
+        } else if let Some(missing_value) = missing_value {
+            quote! {
+                let #value = match parsed_fields.#index {
+                    ::core::option::Option::Some(value) => value,
+                    ::core::option::Option::None => #missing_value,
+                };
+            }
+        } else {
+            required.push(value.clone());
+            quote! {
+                let #value = parsed_fields.#index;
+                if #value.is_none() {
+                    missing.push(#json_name);
+                }
+            }
+        });
+        struct_init_lines.push(quote! { #name: #value });
+    }
+
+    let default_value = if container_default {
+        quote! { let default_value: Self = ::core::default::Default::default(); }
+    } else {
+        quote! {}
+    };
+
+    let construct = quote! {
+        ::core::option::Option::Some(#constructor {
+            #(#struct_init_lines),*
+        })
+    };
+    let result = if required.is_empty() {
+        construct
+    } else {
+        quote! {
+            match (#(#required,)*) {
+                (#(::core::option::Option::Some(#required),)*) => #construct,
+                _ => ::core::option::Option::None,
+            }
+        }
+    };
+
+    quote! {
+        {
+            #default_value
+            #(#value_lines)*
+            #result
+        }
+    }
+}
+
+// Generate an expression which parses an object, returning early from the surrounding function
+// if it fails. `type_name` is reported if the value isn't an object
+// `parsed_fields` starts as `init` of type `fields_type`, `parse_field` is run for each property
+// (giving whether it was a field), and `finish` gives the result once the object has ended (or
+// `None` if properties are missing, after adding them to `missing`)
+fn parse_object_with(
+    type_name: &Ident,
+    fields_type: TokenStream2,
+    init: TokenStream2,
+    parse_field: TokenStream2,
+    finish: TokenStream2,
+    options: &ObjectOptions,
+) -> TokenStream2 {
+    let unknown_property = unknown_property(options.unknown_fields);
+
+    // The tag has already been read, so its value is only checked for being a string
+    let skip_key = match options.skip_key {
+        Some(key) => quote! {
+            #key => {
+                <::std::string::String as _json_parser::Parse>::parse(parser)?;
+                true
+            }
+        },
+        None => quote! {},
+    };
+
+    quote! {
+        {
+            let l_curly_token = parser.advance()?;
+            if l_curly_token.kind != _json_parser::TokenKind::LCurlyBracket {
+                return ::core::result::Result::Err(
+                    parser.make_type_err(::core::stringify!(#type_name), &l_curly_token),
+                );
+            }
+            parser.enter_nested()?;
+            parser.start_object();
+
+            let mut had_comma = false;
+
+            // Temporary object to store field data
+            let mut parsed_fields: #fields_type = #init;
+
+            // Loop through all properties, until reaching closing bracket
+            while !parser.check(_json_parser::TokenKind::RCurlyBracket)? {
+                let token = parser.advance()?;
+                match token.kind {
+                    _json_parser::TokenKind::String(ref key) => {
+                        parser.consume(_json_parser::TokenKind::Colon)?;
+
+                        // Assign the data to parsed_fields
+                        parser.push_path(_json_parser::PathSegment::Key(key.clone()));
+                        let is_field = if parser.check_key(&token)? {
+                            match key.as_str() {
+                                #skip_key
+                                key => #parse_field,
+                            }
+                        } else {
+                            // Repeated key whose earlier value is kept
+                            parser.skip_value()?;
+                            true
+                        };
+                        if !is_field {
+                            #unknown_property
+                        }
+                        parser.pop_path();
+
+                        // Once no comma at end, we have reached end of object
+                        had_comma = parser.check(_json_parser::TokenKind::Comma)?;
+                        if had_comma {
+                            parser.advance()?;
+                        } else {
+                            break;
+                        }
+                    }
+                    _ => {
+                        return ::core::result::Result::Err(
+                            parser.make_err_prev(_json_parser::ParserErrKind::UnexpectedToken),
+                        );
+                    }
+                }
+            }
+
+            // No trailing comma, unless the options allow it
+            if had_comma && !parser.options().allow_trailing_commas {
+                return ::core::result::Result::Err(
+                    parser.make_err_prev(_json_parser::ParserErrKind::UnexpectedToken),
+                );
+            }
+
+            parser.consume(_json_parser::TokenKind::RCurlyBracket)?;
+            parser.end_object();
+            parser.exit_nested();
+
+            // Convert parsed_fields into the user's struct
+            let missing = &mut ::std::vec::Vec::new();
+            match #finish {
+                ::core::option::Option::Some(value) => value,
+                ::core::option::Option::None => {
+                    return ::core::result::Result::Err(
+                        parser.make_missing_err(missing, &l_curly_token),
+                    );
+                }
+            }
+        }
+    }
+}
+
+// Generate an expression which parses an object into `constructor { fields }`, for struct
+// variants of enums (structs go through their `ParseFields` implementation instead)
+pub fn parse_object(
+    type_name: &Ident,
+    constructor: TokenStream2,
+    fields: &[Field],
+    options: &ObjectOptions,
+) -> TokenStream2 {
+    parse_object_with(
+        type_name,
+        parsed_fields_type(fields),
+        parsed_fields_init(fields),
+        parse_field(fields),
+        finish_fields(&constructor, fields, options.container_default),
+        options,
+    )
+}
+
+// Generate code writing each field as a property `"name":value`. Each property is preceded by a
+// comma unless `*__first` is set, which is then cleared, so `__first: &mut bool` must be in scope.
+// The name is reserved so that it can't clash with the bindings of an enum variant's fields
+// `value` gives the expression for a reference to the field's value
+pub fn write_properties(fields: &[Field], value: impl Fn(&Ident) -> TokenStream2) -> TokenStream2 {
+    let separator = quote! {
+        if !*__first {
+            serialiser.write_raw(",");
+        }
+        *__first = false;
+    };
+
+    let mut field_writers = Vec::new();
+    for field in fields.iter().filter(|field| !field.extra) {
+        let json_name = &field.json_name;
+        let value = value(&field.ident);
+
+        if field.flatten {
+            field_writers.push(quote! {
+                _json_parser::SerialiseFields::serialise_fields(#value, serialiser, __first);
+            });
+            continue;
+        }
+
+        field_writers.push(quote! {
+            #separator
+            serialiser.write_string(#json_name);
+            serialiser.write_raw(":");
+            _json_parser::Serialise::serialise(#value, serialiser);
+        });
+    }
+
+    // Extra properties are written after the fields, as if they were fields themselves
+    if let Some(field) = fields.iter().find(|field| field.extra) {
+        let value = value(&field.ident);
+        field_writers.push(quote! {
+            for (key, value) in ::core::iter::IntoIterator::into_iter(#value) {
+                #separator
+                serialiser.write_string(key);
+                serialiser.write_raw(":");
+                _json_parser::Serialise::serialise(value, serialiser);
+            }
+        });
+    }
+
+    quote! { #(#field_writers)* }
+}
+
+// Generate an expression which parses `null` into `constructor`, for unit structs and variants
+pub fn parse_unit(constructor: TokenStream2) -> TokenStream2 {
+    quote! {
+        {
+            let token = parser.advance()?;
+            if token.kind != _json_parser::TokenKind::Null {
+                return ::core::result::Result::Err(parser.make_type_err("null", &token));
+            }
+            #constructor
+        }
+    }
+}
+
+// Generate an expression which parses an array into `constructor(elements)`, for tuple structs
+// and variants. The array must have exactly one element for each field
+pub fn parse_tuple(
+    type_name: &Ident,
+    constructor: TokenStream2,
+    fields: &FieldsUnnamed,
+) -> TokenStream2 {
+    let len = fields.unnamed.len();
+    let mut element_parsers = Vec::new();
+    let mut element_names = Vec::new();
+    for (i, field) in fields.unnamed.iter().enumerate() {
+        let ty = &field.ty;
+        let name = format_ident!("element_{i}");
+
+        element_parsers.push(quote! {
+            parser.tuple_element(#i, #len, &l_bracket_token)?;
+            parser.push_path(_json_parser::PathSegment::Index(#i));
+            let #name = <#ty as _json_parser::Parse>::parse(parser)?;
+            parser.pop_path();
+        });
+        element_names.push(name);
+    }
+
+    quote! {
+        {
+            let l_bracket_token = parser.advance()?;
+            if l_bracket_token.kind != _json_parser::TokenKind::LBracket {
+                return ::core::result::Result::Err(
+                    parser.make_type_err(::core::stringify!(#type_name), &l_bracket_token),
+                );
+            }
+            parser.enter_nested()?;
+
+            #(#element_parsers)*
+
+            parser.end_tuple(#len, &l_bracket_token)?;
+            parser.exit_nested();
+
+            #constructor(#(#element_names),*)
+        }
+    }
+}
+
+// Generate code writing an array of `values`, which are expressions for references to them
+pub fn write_tuple(values: impl Iterator<Item = TokenStream2>) -> TokenStream2 {
+    let element_writers = values.enumerate().map(|(i, value)| {
+        let separator = if i > 0 {
+            quote! { serialiser.write_raw(","); }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #separator
+            _json_parser::Serialise::serialise(#value, serialiser);
+        }
+    });
+
+    quote! {
+        serialiser.write_raw("[");
+        #(#element_writers)*
+        serialiser.write_raw("]");
+    }
+}
+
+pub fn derive_json_deserialise_struct(
+    input: &DeriveInput,
+    data: &DataStruct,
+) -> Result<TokenStream2> {
+    let struct_name = &input.ident;
+    let attrs = ContainerAttrs::parse(&input.attrs, container(data))?;
+    check_unnamed_fields(&data.fields)?;
+    let generics = with_bounds(
+        &input.generics,
+        attrs.bound.as_deref(),
+        quote! { _json_parser::Parse },
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Tuple and unit structs aren't objects, so are parsed on their own
+    let parse_body = match &data.fields {
+        Fields::Named(_) => None,
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let ty = &fields.unnamed[0].ty;
+            Some(quote! { Self(<#ty as _json_parser::Parse>::parse(parser)?) })
+        }
+        Fields::Unnamed(fields) => Some(parse_tuple(struct_name, quote! { Self }, fields)),
+        Fields::Unit => Some(parse_unit(quote! { Self })),
+    };
+    if let Some(parse_body) = parse_body {
+        let generated_impl = quote! {
+            impl #impl_generics _json_parser::Parse for #struct_name #ty_generics #where_clause {
+                fn parse(
+                    parser: &mut _json_parser::Parser,
+                ) -> ::core::result::Result<Self, _json_parser::ParserErr> {
+                    ::core::result::Result::Ok(#parse_body)
+                }
+            }
+        };
+        return Ok(wrap_impls(attrs.crate_path.as_ref(), generated_impl));
+    }
+
+    let fields = fields(&data.fields, attrs.rename_all)?;
+
+    let parsed_fields_type = parsed_fields_type(&fields);
+    let parsed_fields_init = parsed_fields_init(&fields);
+    let parse_field = parse_field(&fields);
+    let finish_fields = finish_fields(&quote! { #struct_name }, &fields, attrs.default);
+
+    let options = ObjectOptions {
+        unknown_fields: attrs.unknown_fields,
+        ..Default::default()
+    };
+    let parse_object = parse_object_with(
+        struct_name,
+        quote! { <Self as _json_parser::ParseFields>::Fields },
+        quote! { <Self as _json_parser::ParseFields>::empty_fields() },
+        quote! {
+            <Self as _json_parser::ParseFields>::parse_field(&mut parsed_fields, key, parser)?
+        },
+        quote! { <Self as _json_parser::ParseFields>::finish_fields(parsed_fields, missing) },
+        &options,
+    );
+
+    // Generated impl blocks
+    // The fields are parsed through `ParseFields`, so other structs can flatten this one
+    let generated_impl = quote! {
+        #[allow(unused_variables, clippy::unused_unit)]
+        impl #impl_generics _json_parser::ParseFields for #struct_name #ty_generics #where_clause {
+            type Fields = #parsed_fields_type;
+
+            fn empty_fields() -> Self::Fields {
+                #parsed_fields_init
+            }
+
+            fn parse_field(
+                parsed_fields: &mut Self::Fields,
+                key: &str,
+                parser: &mut _json_parser::Parser,
+            ) -> ::core::result::Result<bool, _json_parser::ParserErr> {
+                ::core::result::Result::Ok(#parse_field)
+            }
+
+            fn finish_fields(
+                parsed_fields: Self::Fields,
+                missing: &mut ::std::vec::Vec<&'static str>,
+            ) -> ::core::option::Option<Self> {
+                #finish_fields
+            }
+        }
+
+        impl #impl_generics _json_parser::Parse for #struct_name #ty_generics #where_clause {
+            fn parse(
+                parser: &mut _json_parser::Parser,
+            ) -> ::core::result::Result<Self, _json_parser::ParserErr> {
+                ::core::result::Result::Ok(#parse_object)
+            }
+        }
+    };
+
+    Ok(wrap_impls(attrs.crate_path.as_ref(), generated_impl))
+}
+
+pub fn derive_json_serialise_struct(
+    input: &DeriveInput,
+    data: &DataStruct,
+) -> Result<TokenStream2> {
+    let struct_name = &input.ident;
+    let attrs = ContainerAttrs::parse(&input.attrs, container(data))?;
+    check_unnamed_fields(&data.fields)?;
+    let generics = with_bounds(
+        &input.generics,
+        attrs.bound.as_deref(),
+        quote! { _json_parser::Serialise },
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // The reverse of parsing tuple and unit structs
+    let serialise_body = match &data.fields {
+        Fields::Named(_) => None,
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            Some(quote! { _json_parser::Serialise::serialise(&self.0, serialiser); })
+        }
+        Fields::Unnamed(fields) => Some(write_tuple((0..fields.unnamed.len()).map(|i| {
+            let index = Index::from(i);
+            quote! { &self.#index }
+        }))),
+        Fields::Unit => Some(quote! { serialiser.write_raw("null"); }),
+    };
+    if let Some(serialise_body) = serialise_body {
+        let generated_impl = quote! {
+            impl #impl_generics _json_parser::Serialise for #struct_name #ty_generics #where_clause {
+                fn serialise(&self, serialiser: &mut _json_parser::Serialiser) {
+                    #serialise_body
+                }
+            }
+        };
+        return Ok(wrap_impls(attrs.crate_path.as_ref(), generated_impl));
+    }
+
+    let fields = fields(&data.fields, attrs.rename_all)?;
+    let field_writers = write_properties(&fields, |name| quote! { &self.#name });
+
+    // Generated impl blocks
+    // The fields are written through `SerialiseFields`, so other structs can flatten this one
+    let generated_impl = quote! {
+        #[allow(unused_variables)]
+        impl #impl_generics _json_parser::SerialiseFields for #struct_name #ty_generics #where_clause {
+            fn serialise_fields(
+                &self,
+                serialiser: &mut _json_parser::Serialiser,
+                __first: &mut bool,
+            ) {
+                #field_writers
+            }
+        }
+
+        impl #impl_generics _json_parser::Serialise for #struct_name #ty_generics #where_clause {
+            fn serialise(&self, serialiser: &mut _json_parser::Serialiser) {
+                serialiser.write_raw("{");
+                _json_parser::SerialiseFields::serialise_fields(self, serialiser, &mut true);
+                serialiser.write_raw("}");
+            }
+        }
+    };
+
+    Ok(wrap_impls(attrs.crate_path.as_ref(), generated_impl))
+}
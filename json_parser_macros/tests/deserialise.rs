@@ -0,0 +1,1162 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use json_parser::{json_value::JsonValue, *};
+use json_parser_macros::JsonDeserialise;
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct User {
+    name: String,
+    history: Vec<History>,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct History {
+    ip: String,
+    success: bool,
+}
+
+#[test]
+fn test_nested() {
+    let result = Parser::parse::<User>(
+        r#"{"name": "Jane", "history": [{"ip": "192.168.1.1", "success": true}]}"#,
+    );
+    assert_eq!(
+        Ok(User {
+            name: "Jane".to_string(),
+            history: vec![History {
+                ip: "192.168.1.1".to_string(),
+                success: true,
+            }],
+        }),
+        result
+    );
+}
+
+#[test]
+fn test_invalid_type() {
+    let result = Parser::parse::<User>(r#"["Jane"]"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::InvalidType {
+            expected: "User",
+            found: JsonType::Array,
+        },
+        result.kind
+    );
+}
+
+#[test]
+fn test_missing_property() {
+    let result = Parser::parse::<History>(r#"{"ip": "192.168.1.1"}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["success".to_string()]),
+        result.kind
+    );
+}
+
+#[test]
+fn test_missing_properties() {
+    let source = "{\n  \"history\": []\n}";
+    let err = Parser::parse::<User>(source).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["name".to_string()]),
+        err.kind
+    );
+    assert_eq!("{", err.lexeme);
+    assert_eq!(
+        (0, source.len()),
+        (err.span.start.offset, err.span.end.offset)
+    );
+
+    // Every missing property is reported together, across the whole object
+    let err = Parser::parse::<History>(r#"{ }"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["ip".to_string(), "success".to_string()]),
+        err.kind
+    );
+    assert_eq!("{", err.lexeme);
+    assert_eq!((0, 3), (err.span.start.offset, err.span.end.offset));
+
+    let err = Parser::parse::<User>(r#"{"history": [{}]}"#).unwrap_err();
+    assert_eq!("$.history[0]", err.path.to_string());
+    assert_eq!((13, 15), (err.span.start.offset, err.span.end.offset));
+
+    let err = Parser::parse::<Event>(r#"{"Click": {}}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["x".to_string(), "y".to_string()]),
+        err.kind
+    );
+}
+
+#[test]
+fn test_unknown_property() {
+    let result =
+        Parser::parse::<History>(r#"{"ip": "", "success": true, "port": 80}"#).unwrap_err();
+    assert_eq!(ParserErrKind::UnknownProperty, result.kind);
+    assert_eq!("$.port", result.path.to_string());
+}
+
+#[test]
+fn test_error_path() {
+    let source = r#"{
+        "name": "Jane",
+        "history": [
+            {"ip": "1", "success": true},
+            {"ip": "2", "success": true},
+            {"ip": "3", "success": false},
+            {"ip": "4", "success": "yes"}
+        ]
+    }"#;
+
+    let result = Parser::parse::<User>(source).unwrap_err();
+    assert_eq!(
+        ParserErrKind::InvalidType {
+            expected: "bool",
+            found: JsonType::String,
+        },
+        result.kind
+    );
+    assert_eq!("$.history[3].success", result.path.to_string());
+}
+
+#[test]
+fn test_missing_property_path() {
+    let result =
+        Parser::parse::<User>(r#"{"name": "Jane", "history": [{"ip": "1"}]}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["success".to_string()]),
+        result.kind
+    );
+    assert_eq!("$.history[0]", result.path.to_string());
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Node {
+    children: Vec<Node>,
+}
+
+#[test]
+fn test_depth_limit() {
+    let options = ParseOptions::new().max_depth(4);
+
+    // Each node is an object and an array
+    let result = Parser::parse_with::<Node>(r#"{"children": [{"children": []}]}"#, &options);
+    assert!(result.is_ok());
+
+    let result = Parser::parse_with::<Node>(
+        r#"{"children": [{"children": [{"children": []}]}]}"#,
+        &options,
+    );
+    assert_eq!(
+        Err(ParserErrKind::DepthLimitExceeded),
+        result.map_err(|x| x.kind)
+    );
+}
+
+#[test]
+fn test_trailing_comma() {
+    let source = r#"{"ip": "1", "success": true,}"#;
+
+    let result = Parser::parse::<History>(source);
+    assert_eq!(
+        Err(ParserErrKind::UnexpectedToken),
+        result.map_err(|x| x.kind)
+    );
+
+    let result =
+        Parser::parse_with::<History>(source, &ParseOptions::new().allow_trailing_commas(true));
+    assert_eq!(
+        Ok(History {
+            ip: "1".to_string(),
+            success: true,
+        }),
+        result
+    );
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+enum Theme {
+    #[json(rename = "dark")]
+    Dark,
+    #[json(rename = "light")]
+    Light,
+    System,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Preferences {
+    theme: Theme,
+}
+
+#[test]
+fn test_unit_enum() {
+    assert_eq!(Ok(Theme::Dark), Parser::parse::<Theme>(r#""dark""#));
+    assert_eq!(Ok(Theme::Light), Parser::parse::<Theme>(r#""light""#));
+    assert_eq!(Ok(Theme::System), Parser::parse::<Theme>(r#""System""#));
+}
+
+#[test]
+fn test_unit_enum_unknown_variant() {
+    // Renamed variants can only be matched by their new name
+    let result = Parser::parse::<Preferences>(r#"{"theme": "Dark"}"#);
+    let err = result.unwrap_err();
+    assert_eq!(
+        ParserErrKind::UnknownVariant {
+            found: "Dark".to_string(),
+            expected: &["dark", "light", "System"],
+        },
+        err.kind
+    );
+    assert_eq!("$.theme", err.path.to_string());
+}
+
+#[test]
+fn test_unit_enum_invalid_type() {
+    let result = Parser::parse::<Theme>("1");
+    assert_eq!(
+        Err(ParserErrKind::InvalidType {
+            expected: "Theme",
+            found: JsonType::Number,
+        }),
+        result.map_err(|x| x.kind)
+    );
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+enum Event {
+    Close,
+    Key(String),
+    Move(i32, i32),
+    Click { x: i32, y: i32 },
+}
+
+#[test]
+fn test_externally_tagged_enum() {
+    let cases = vec![
+        (r#""Close""#, Event::Close),
+        // Written by some other serialisers
+        (r#"{"Close": null}"#, Event::Close),
+        (r#"{"Key": "a"}"#, Event::Key("a".to_string())),
+        (r#"{"Move": [1, -2]}"#, Event::Move(1, -2)),
+        (
+            r#"{"Click": {"y": 2, "x": 1}}"#,
+            Event::Click { x: 1, y: 2 },
+        ),
+    ];
+
+    for (source, expected) in cases {
+        assert_eq!(Ok(expected), Parser::parse::<Event>(source));
+    }
+}
+
+#[test]
+fn test_externally_tagged_enum_errors() {
+    let err = Parser::parse::<Event>(r#"{"Scroll": 5}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::UnknownVariant {
+            found: "Scroll".to_string(),
+            expected: &["Close", "Key", "Move", "Click"],
+        },
+        err.kind
+    );
+
+    let err = Parser::parse::<Event>(r#"{"Click": {"x": 1, "y": true}}"#).unwrap_err();
+    assert_eq!("$.Click.y", err.path.to_string());
+
+    let err = Parser::parse::<Event>(r#"{"Move": [1, 2, 3]}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::InvalidLength {
+            expected: 2,
+            found: 3,
+        },
+        err.kind
+    );
+    assert_eq!("$.Move", err.path.to_string());
+
+    let result = Parser::parse::<Event>("5");
+    assert_eq!(
+        Err(ParserErrKind::InvalidType {
+            expected: "Event",
+            found: JsonType::Number,
+        }),
+        result.map_err(|x| x.kind)
+    );
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(tag = "type")]
+enum Action {
+    #[json(rename = "click")]
+    Click { x: i32, y: i32 },
+    #[json(rename = "close")]
+    Close,
+}
+
+#[test]
+fn test_internally_tagged_enum() {
+    let cases = vec![
+        (
+            r#"{"type": "click", "x": 1, "y": 2}"#,
+            Action::Click { x: 1, y: 2 },
+        ),
+        // The tag doesn't have to come first
+        (
+            r#"{"x": 1, "y": 2, "type": "click"}"#,
+            Action::Click { x: 1, y: 2 },
+        ),
+        (r#"{"type": "close"}"#, Action::Close),
+    ];
+
+    for (source, expected) in cases {
+        assert_eq!(Ok(expected), Parser::parse::<Action>(source));
+    }
+}
+
+#[test]
+fn test_internally_tagged_enum_errors() {
+    let err = Parser::parse::<Action>(r#"{"x": 1, "type": "scroll"}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::UnknownVariant {
+            found: "scroll".to_string(),
+            expected: &["click", "close"],
+        },
+        err.kind
+    );
+    assert_eq!("$.type", err.path.to_string());
+
+    let err = Parser::parse::<Action>(r#"{"x": 1, "y": 2}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["type".to_string()]),
+        err.kind
+    );
+
+    let err = Parser::parse::<Action>(r#"{"type": "close", "x": 1}"#).unwrap_err();
+    assert_eq!(ParserErrKind::UnknownProperty, err.kind);
+    assert_eq!("$.x", err.path.to_string());
+
+    let err = Parser::parse::<Action>(r#"{"type": "click", "x": 1}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["y".to_string()]),
+        err.kind
+    );
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(tag = "t", content = "c")]
+enum Message {
+    Ping,
+    Text(String),
+    Point(i32, i32),
+    Resize { width: u32, height: u32 },
+}
+
+#[test]
+fn test_adjacently_tagged_enum() {
+    let cases = vec![
+        (r#"{"t": "Ping"}"#, Message::Ping),
+        (r#"{"t": "Ping", "c": null}"#, Message::Ping),
+        (
+            r#"{"t": "Text", "c": "hi"}"#,
+            Message::Text("hi".to_string()),
+        ),
+        // The content can come before the tag
+        (r#"{"c": [3, 4], "t": "Point"}"#, Message::Point(3, 4)),
+        (
+            r#"{"t": "Resize", "c": {"width": 10, "height": 20}}"#,
+            Message::Resize {
+                width: 10,
+                height: 20,
+            },
+        ),
+    ];
+
+    for (source, expected) in cases {
+        assert_eq!(Ok(expected), Parser::parse::<Message>(source));
+    }
+}
+
+#[test]
+fn test_adjacently_tagged_enum_errors() {
+    let err = Parser::parse::<Message>(r#"{"t": "Text"}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["c".to_string()]),
+        err.kind
+    );
+
+    let err = Parser::parse::<Message>(r#"{"c": 5, "t": "Text"}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::InvalidType {
+            expected: "String",
+            found: JsonType::Number,
+        },
+        err.kind
+    );
+    assert_eq!("$.c", err.path.to_string());
+
+    let err = Parser::parse::<Message>(r#"{"t": "Ping", "other": 1}"#).unwrap_err();
+    assert_eq!(ParserErrKind::UnknownProperty, err.kind);
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(untagged)]
+enum Value {
+    Missing,
+    Flag(bool),
+    Pair(i32, i32),
+    Point { x: i32, y: i32 },
+    Text(String),
+}
+
+#[test]
+fn test_untagged_enum() {
+    let cases = vec![
+        ("null", Value::Missing),
+        ("true", Value::Flag(true)),
+        ("[1, 2]", Value::Pair(1, 2)),
+        (r#"{"x": 1, "y": 2}"#, Value::Point { x: 1, y: 2 }),
+        (r#""text""#, Value::Text("text".to_string())),
+    ];
+
+    for (source, expected) in cases {
+        assert_eq!(Ok(expected), Parser::parse::<Value>(source));
+    }
+
+    // Backtracking out of a variant which failed part way through
+    let result = Parser::parse::<Vec<Value>>(r#"[{"x": 1, "y": 2}, [1, 2], {"x": 1}]"#);
+    let err = result.unwrap_err();
+    assert_eq!(ParserErrKind::NoMatchingVariant("Value"), err.kind);
+    assert_eq!("$[2]", err.path.to_string());
+    assert_eq!(27, err.span.start.offset);
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(untagged)]
+enum Nested {
+    Deep(Vec<Vec<u8>>),
+    Flat(Vec<bool>),
+}
+
+#[test]
+fn test_untagged_enum_errors() {
+    // The error from the variant that got furthest is more useful than no variant matching
+    let err = Parser::parse::<Value>(r#"{"x": 1, "y": "2"}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::InvalidType {
+            expected: "i32",
+            found: JsonType::String,
+        },
+        err.kind
+    );
+    assert_eq!("$.y", err.path.to_string());
+
+    let err = Parser::parse::<Value>("[1, true]").unwrap_err();
+    assert_eq!("$[1]", err.path.to_string());
+
+    // Invalid JSON is reported as it is, rather than trying the other variants
+    let err = Parser::parse::<Value>(r#"{"x": 01}"#).unwrap_err();
+    assert_eq!(ParserErrKind::LeadingZero, err.kind);
+
+    let options = ParseOptions::new().max_depth(1);
+    let err = Parser::parse_with::<Nested>("[[1]]", &options).unwrap_err();
+    assert_eq!(ParserErrKind::DepthLimitExceeded, err.kind);
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(rename_all = "camelCase")]
+struct Account {
+    is_verified: bool,
+    display_name: String,
+    #[json(rename = "ID")]
+    id: u32,
+}
+
+#[test]
+fn test_rename() {
+    let result =
+        Parser::parse::<Account>(r#"{"isVerified": true, "displayName": "Jane", "ID": 4}"#);
+    assert_eq!(
+        Ok(Account {
+            is_verified: true,
+            display_name: "Jane".to_string(),
+            id: 4,
+        }),
+        result
+    );
+
+    // The Rust names are no longer recognised
+    let err = Parser::parse::<Account>(r#"{"is_verified": true}"#).unwrap_err();
+    assert_eq!(ParserErrKind::UnknownProperty, err.kind);
+}
+
+#[test]
+fn test_rename_missing_property() {
+    let result = Parser::parse::<Account>(r#"{"isVerified": true, "ID": 4}"#);
+    assert_eq!(
+        Err(ParserErrKind::MissingProperties(vec![
+            "displayName".to_string()
+        ])),
+        result.map_err(|x| x.kind)
+    );
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(rename_all = "SCREAMING_SNAKE_CASE")]
+struct Screaming {
+    max_value: u32,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(rename_all = "kebab-case")]
+struct Kebab {
+    max_value: u32,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(rename_all = "PascalCase")]
+struct Pascal {
+    max_value: u32,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(rename_all = "snake_case", tag = "kind")]
+enum Shape {
+    UnitCircle,
+    #[json(rename = "rect")]
+    Rectangle {
+        #[json(rename = "w")]
+        width: u32,
+    },
+}
+
+#[test]
+fn test_rename_all() {
+    assert_eq!(
+        Ok(Screaming { max_value: 1 }),
+        Parser::parse(r#"{"MAX_VALUE": 1}"#)
+    );
+    assert_eq!(
+        Ok(Kebab { max_value: 1 }),
+        Parser::parse(r#"{"max-value": 1}"#)
+    );
+    assert_eq!(
+        Ok(Pascal { max_value: 1 }),
+        Parser::parse(r#"{"MaxValue": 1}"#)
+    );
+
+    // Renames variants of enums
+    assert_eq!(
+        Ok(Shape::UnitCircle),
+        Parser::parse(r#"{"kind": "unit_circle"}"#)
+    );
+    assert_eq!(
+        Ok(Shape::Rectangle { width: 3 }),
+        Parser::parse(r#"{"kind": "rect", "w": 3}"#)
+    );
+}
+
+fn default_port() -> u16 {
+    8080
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Server {
+    host: String,
+    nickname: Option<String>,
+    #[json(default)]
+    tags: Vec<String>,
+    #[json(default = "default_port")]
+    port: u16,
+}
+
+#[test]
+fn test_optional_fields() {
+    let result = Parser::parse::<Server>(r#"{"host": "localhost"}"#);
+    assert_eq!(
+        Ok(Server {
+            host: "localhost".to_string(),
+            nickname: None,
+            tags: Vec::new(),
+            port: 8080,
+        }),
+        result
+    );
+
+    let result = Parser::parse::<Server>(
+        r#"{"host": "localhost", "nickname": "local", "tags": ["a"], "port": 80}"#,
+    );
+    assert_eq!(
+        Ok(Server {
+            host: "localhost".to_string(),
+            nickname: Some("local".to_string()),
+            tags: vec!["a".to_string()],
+            port: 80,
+        }),
+        result
+    );
+
+    // Fields without a default are still required
+    let result = Parser::parse::<Server>(r#"{"port": 80}"#);
+    assert_eq!(
+        Err(ParserErrKind::MissingProperties(vec!["host".to_string()])),
+        result.map_err(|x| x.kind)
+    );
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(default)]
+struct Settings {
+    volume: u8,
+    muted: bool,
+    #[json(default)]
+    name: String,
+    device: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            volume: 50,
+            muted: true,
+            name: "unused".to_string(),
+            device: Some("speakers".to_string()),
+        }
+    }
+}
+
+#[test]
+fn test_container_default() {
+    let result = Parser::parse::<Settings>(r#"{"muted": false}"#);
+    assert_eq!(
+        Ok(Settings {
+            volume: 50,
+            muted: false,
+            // Field defaults take priority over the container's
+            name: String::new(),
+            device: Some("speakers".to_string()),
+        }),
+        result
+    );
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(ignore_unknown_fields)]
+struct Lenient {
+    id: u32,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(deny_unknown_fields)]
+struct Strict {
+    id: u32,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(tag = "type", ignore_unknown_fields)]
+enum LenientEvent {
+    Click { x: i32 },
+}
+
+#[test]
+fn test_ignore_unknown_fields() {
+    let source = r#"{"added": {"nested": [1, {"a": null}]}, "id": 3, "other": "x"}"#;
+    assert_eq!(Ok(Lenient { id: 3 }), Parser::parse::<Lenient>(source));
+
+    // The ignored values must still be valid JSON
+    let result = Parser::parse::<Lenient>(r#"{"added": [1, 2, }, "id": 3}"#);
+    let err = result.unwrap_err();
+    assert_eq!(ParserErrKind::UnexpectedToken, err.kind);
+    assert_eq!("$.added[2]", err.path.to_string());
+
+    let result = Parser::parse::<LenientEvent>(r#"{"type": "Click", "x": 1, "y": 2}"#);
+    assert_eq!(Ok(LenientEvent::Click { x: 1 }), result);
+}
+
+#[test]
+fn test_unknown_fields_option() {
+    let source = r#"{"id": 3, "added": true}"#;
+    let options = ParseOptions::new().unknown_fields(UnknownFields::Ignore);
+
+    // Denied by default
+    let result = Parser::parse::<History>(r#"{"ip": "1", "success": true, "added": 1}"#);
+    assert_eq!(
+        Err(ParserErrKind::UnknownProperty),
+        result.map_err(|x| x.kind)
+    );
+
+    let result =
+        Parser::parse_with::<History>(r#"{"ip": "1", "success": true, "added": 1}"#, &options);
+    assert_eq!(
+        Ok(History {
+            ip: "1".to_string(),
+            success: true,
+        }),
+        result
+    );
+
+    // The attribute takes priority over the option
+    let result = Parser::parse_with::<Strict>(source, &options);
+    assert_eq!(
+        Err(ParserErrKind::UnknownProperty),
+        result.map_err(|x| x.kind)
+    );
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Forwarded {
+    id: u32,
+    #[json(extra)]
+    rest: HashMap<String, JsonValue>,
+}
+
+#[test]
+fn test_extra_fields() {
+    let result = Parser::parse::<Forwarded>(r#"{"a": [1], "id": 3, "b": {"c": null}}"#);
+    assert_eq!(
+        Ok(Forwarded {
+            id: 3,
+            rest: HashMap::from([
+                (
+                    "a".to_string(),
+                    JsonValue::Array(vec![JsonValue::Number(1.0)])
+                ),
+                (
+                    "b".to_string(),
+                    JsonValue::Object(HashMap::from([("c".to_string(), JsonValue::Null)]))
+                ),
+            ]),
+        }),
+        result
+    );
+
+    let result = Parser::parse::<Forwarded>(r#"{"id": 3}"#);
+    assert_eq!(
+        Ok(Forwarded {
+            id: 3,
+            rest: HashMap::new(),
+        }),
+        result
+    );
+
+    // Named fields are still required
+    let result = Parser::parse::<Forwarded>(r#"{"a": 1}"#);
+    assert_eq!(
+        Err(ParserErrKind::MissingProperties(vec!["id".to_string()])),
+        result.map_err(|x| x.kind)
+    );
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct TypedExtra {
+    #[json(extra)]
+    counts: HashMap<String, u32>,
+}
+
+#[test]
+fn test_extra_fields_typed() {
+    let result = Parser::parse::<TypedExtra>(r#"{"a": 1, "b": 2}"#);
+    assert_eq!(
+        Ok(TypedExtra {
+            counts: HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)]),
+        }),
+        result
+    );
+
+    let err = Parser::parse::<TypedExtra>(r#"{"a": 1, "b": "2"}"#).unwrap_err();
+    assert_eq!("$.b", err.path.to_string());
+}
+
+#[derive(Debug, Default, PartialEq, JsonDeserialise)]
+#[json(rename_all = "camelCase")]
+struct Metadata {
+    id: u32,
+    created_at: String,
+    version: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Document {
+    title: String,
+    #[json(flatten)]
+    metadata: Metadata,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(tag = "kind")]
+enum Resource {
+    Document {
+        #[json(flatten)]
+        metadata: Metadata,
+        #[json(extra)]
+        rest: HashMap<String, u32>,
+    },
+}
+
+#[test]
+fn test_flatten() {
+    let source = r#"{"id": 1, "title": "Notes", "createdAt": "today"}"#;
+    assert_eq!(
+        Ok(Document {
+            title: "Notes".to_string(),
+            metadata: Metadata {
+                id: 1,
+                created_at: "today".to_string(),
+                version: None,
+            },
+        }),
+        Parser::parse::<Document>(source)
+    );
+
+    // Properties matching neither struct are still unknown
+    let source = r#"{"id": 1, "title": "Notes", "createdAt": "today", "other": 2}"#;
+    let err = Parser::parse::<Document>(source).unwrap_err();
+    assert_eq!(ParserErrKind::UnknownProperty, err.kind);
+    assert_eq!("$.other", err.path.to_string());
+
+    let source = r#"{"kind": "Document", "id": 1, "createdAt": "today", "other": 2}"#;
+    assert_eq!(
+        Ok(Resource::Document {
+            metadata: Metadata {
+                id: 1,
+                created_at: "today".to_string(),
+                version: None,
+            },
+            rest: HashMap::from([("other".to_string(), 2)]),
+        }),
+        Parser::parse::<Resource>(source)
+    );
+}
+
+#[test]
+fn test_flatten_errors() {
+    // Missing inner fields are reported with their JSON name, at the parent object
+    let err = Parser::parse::<Document>(r#"{"title": "Notes", "id": 1}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["createdAt".to_string()]),
+        err.kind
+    );
+    assert_eq!("{", err.lexeme);
+    assert_eq!("$", err.path.to_string());
+
+    let err = Parser::parse::<Document>(r#"{"id": 1, "createdAt": "today"}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["title".to_string()]),
+        err.kind
+    );
+
+    // Type errors in inner fields are reported at the property in the parent object
+    let source = r#"{"title": "Notes", "id": 1, "createdAt": 2}"#;
+    let err = Parser::parse::<Document>(source).unwrap_err();
+    assert_eq!("$.createdAt", err.path.to_string());
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Draft {
+    title: String,
+    #[json(flatten, default)]
+    metadata: Metadata,
+}
+
+#[derive(Debug, Default, PartialEq, JsonDeserialise)]
+#[json(default)]
+struct Template {
+    title: String,
+    #[json(flatten)]
+    metadata: Metadata,
+}
+
+#[test]
+fn test_flatten_default() {
+    let source = r#"{"title": "Notes", "id": 1}"#;
+    let expected = Draft {
+        title: "Notes".to_string(),
+        metadata: Metadata::default(),
+    };
+    assert_eq!(Ok(expected), Parser::parse::<Draft>(source));
+
+    let expected = Template {
+        title: "Notes".to_string(),
+        metadata: Metadata::default(),
+    };
+    assert_eq!(Ok(expected), Parser::parse::<Template>(source));
+
+    // Only the fields which don't have a default are reported
+    let err = Parser::parse::<Draft>(r#"{"id": 1}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::MissingProperties(vec!["title".to_string()]),
+        err.kind
+    );
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Page<T> {
+    items: Vec<T>,
+    total: u64,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(tag = "status")]
+enum Reply<T, E>
+where
+    E: std::fmt::Debug,
+{
+    Ok { value: T },
+    Failed { error: E },
+}
+
+// Only used through a `Vec`, so the automatic `T: Parse` bound is more than needed
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(bound = "Vec<T>: Parse")]
+struct Batch<T> {
+    values: Vec<T>,
+}
+
+// A type with a lifetime, which is always parsed as owned
+#[derive(Debug, PartialEq)]
+struct Label<'a>(Cow<'a, str>);
+
+impl Parse for Label<'_> {
+    fn parse(parser: &mut Parser) -> Result<Self, ParserErr> {
+        Ok(Label(Cow::Owned(String::parse(parser)?)))
+    }
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Labelled<'a, T> {
+    label: Label<'a>,
+    value: T,
+}
+
+trait Schema {
+    type Id;
+}
+
+// Doesn't implement `Parse` itself, only its `Id` does
+#[derive(Debug, PartialEq)]
+struct Users;
+
+impl Schema for Users {
+    type Id = u32;
+}
+
+// Only used through an associated type, so the automatic `S: Parse` bound would be wrong
+#[derive(Debug, PartialEq, JsonDeserialise)]
+#[json(bound = "S::Id: Parse")]
+struct Record<S: Schema> {
+    id: S::Id,
+}
+
+#[test]
+fn test_generics() {
+    let result =
+        Parser::parse::<Page<History>>(r#"{"items": [{"ip": "1", "success": true}], "total": 10}"#);
+    assert_eq!(
+        Ok(Page {
+            items: vec![History {
+                ip: "1".to_string(),
+                success: true,
+            }],
+            total: 10,
+        }),
+        result
+    );
+
+    // The type parameter is checked like any other type
+    let err = Parser::parse::<Page<u8>>(r#"{"items": [1, "2"], "total": 2}"#).unwrap_err();
+    assert_eq!("$.items[1]", err.path.to_string());
+
+    let result = Parser::parse::<Reply<u32, String>>(r#"{"status": "Failed", "error": "x"}"#);
+    assert_eq!(
+        Ok(Reply::Failed {
+            error: "x".to_string()
+        }),
+        result
+    );
+
+    let result = Parser::parse::<Batch<bool>>(r#"{"values": [true]}"#);
+    assert_eq!(Ok(Batch { values: vec![true] }), result);
+
+    let result = Parser::parse::<Labelled<u8>>(r#"{"label": "size", "value": 3}"#);
+    assert_eq!(
+        Ok(Labelled {
+            label: Label(Cow::Borrowed("size")),
+            value: 3,
+        }),
+        result
+    );
+
+    let result = Parser::parse::<Record<Users>>(r#"{"id": 7}"#);
+    assert_eq!(Ok(Record::<Users> { id: 7 }), result);
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct UserId(u64);
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Range(u32, u32);
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Nothing;
+
+#[derive(Debug, PartialEq, JsonDeserialise)]
+struct Selection {
+    owner: UserId,
+    range: Range,
+    extra: Nothing,
+}
+
+#[test]
+fn test_tuple_structs() {
+    let result = Parser::parse::<Selection>(r#"{"owner": 7, "range": [1, 5], "extra": null}"#);
+    assert_eq!(
+        Ok(Selection {
+            owner: UserId(7),
+            range: Range(1, 5),
+            extra: Nothing,
+        }),
+        result
+    );
+
+    // Newtypes are the same as their inner value, including its errors
+    let err = Parser::parse::<UserId>(r#""7""#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::InvalidType {
+            expected: "u64",
+            found: JsonType::String,
+        },
+        err.kind
+    );
+
+    let err = Parser::parse::<Nothing>("{}").unwrap_err();
+    assert_eq!(
+        ParserErrKind::InvalidType {
+            expected: "null",
+            found: JsonType::Object,
+        },
+        err.kind
+    );
+}
+
+#[test]
+fn test_tuple_struct_errors() {
+    let cases = vec![("[]", 0), ("[1]", 1), ("[1, 2, 3]", 3)];
+    for (source, found) in cases {
+        let err = Parser::parse::<Range>(source).unwrap_err();
+        assert_eq!(
+            ParserErrKind::InvalidLength { expected: 2, found },
+            err.kind
+        );
+        assert_eq!("[", err.lexeme);
+    }
+
+    let err = Parser::parse::<Selection>(r#"{"owner": 7, "range": [1, "5"], "extra": null}"#)
+        .unwrap_err();
+    assert_eq!("$.range[1]", err.path.to_string());
+
+    let err = Parser::parse::<Range>(r#"{"start": 1}"#).unwrap_err();
+    assert_eq!(
+        ParserErrKind::InvalidType {
+            expected: "Range",
+            found: JsonType::Object,
+        },
+        err.kind
+    );
+}
+
+#[test]
+fn test_duplicate_keys() {
+    let source = r#"{"ip": "a", "success": true, "ip": "b"}"#;
+    let parse = |duplicate_keys| {
+        Parser::parse_with::<History>(source, &ParseOptions::new().duplicate_keys(duplicate_keys))
+    };
+    let history = |ip: &str| History {
+        ip: ip.to_string(),
+        success: true,
+    };
+
+    assert_eq!(Ok(history("b")), parse(DuplicateKeys::LastWins));
+    assert_eq!(Ok(history("a")), parse(DuplicateKeys::FirstWins));
+
+    let err = parse(DuplicateKeys::Error).unwrap_err();
+    assert_eq!(
+        ParserErrKind::DuplicateProperty {
+            key: "ip".to_string(),
+            first: Box::new(Span {
+                start: Position {
+                    line: 1,
+                    column: 2,
+                    column_utf16: 2,
+                    offset: 1,
+                },
+                end: Position {
+                    line: 1,
+                    column: 6,
+                    column_utf16: 6,
+                    offset: 5,
+                },
+            }),
+        },
+        err.kind
+    );
+    assert_eq!(29, err.span.start.offset);
+    assert_eq!("$.ip", err.path.to_string());
+}
+
+#[test]
+fn test_duplicate_keys_flatten_and_extra() {
+    let options = ParseOptions::new().duplicate_keys(DuplicateKeys::Error);
+
+    // Fields of flattened structs share their keys with the parent object
+    let source = r#"{"id": 1, "title": "Notes", "createdAt": "today", "id": 2}"#;
+    let err = Parser::parse_with::<Document>(source, &options).unwrap_err();
+    assert!(matches!(err.kind, ParserErrKind::DuplicateProperty { .. }));
+    assert_eq!("$.id", err.path.to_string());
+
+    let source = r#"{"id": 1, "a": 2, "a": 3}"#;
+    let err = Parser::parse_with::<Forwarded>(source, &options).unwrap_err();
+    assert!(matches!(err.kind, ParserErrKind::DuplicateProperty { .. }));
+
+    let options = ParseOptions::new().duplicate_keys(DuplicateKeys::FirstWins);
+    let result = Parser::parse_with::<Forwarded>(source, &options);
+    assert_eq!(
+        Ok(Forwarded {
+            id: 1,
+            rest: HashMap::from([("a".to_string(), JsonValue::Number(2.0))]),
+        }),
+        result
+    );
+}
+
+#[test]
+fn test_duplicate_keys_tagged_enums() {
+    let source = r#"{"type": "close", "type": "click", "x": 1, "y": 2}"#;
+    let parse = |duplicate_keys| {
+        Parser::parse_with::<Action>(source, &ParseOptions::new().duplicate_keys(duplicate_keys))
+    };
+    assert_eq!(
+        Ok(Action::Click { x: 1, y: 2 }),
+        parse(DuplicateKeys::LastWins)
+    );
+    assert_eq!(
+        Err(ParserErrKind::UnknownProperty),
+        parse(DuplicateKeys::FirstWins).map_err(|x| x.kind)
+    );
+    assert!(matches!(
+        parse(DuplicateKeys::Error).map_err(|x| x.kind),
+        Err(ParserErrKind::DuplicateProperty { .. })
+    ));
+
+    let source = r#"{"t": "Text", "c": "a", "c": "b"}"#;
+    let parse = |duplicate_keys| {
+        Parser::parse_with::<Message>(source, &ParseOptions::new().duplicate_keys(duplicate_keys))
+    };
+    assert_eq!(
+        Ok(Message::Text("b".to_string())),
+        parse(DuplicateKeys::LastWins)
+    );
+    assert_eq!(
+        Ok(Message::Text("a".to_string())),
+        parse(DuplicateKeys::FirstWins)
+    );
+    let err = parse(DuplicateKeys::Error).unwrap_err();
+    assert!(matches!(err.kind, ParserErrKind::DuplicateProperty { .. }));
+    assert_eq!("$.c", err.path.to_string());
+}
@@ -0,0 +1,63 @@
+// The generated code must work without importing anything from `json_parser`, even when the
+// caller has its own items with the same names
+use json_parser_macros::{JsonDeserialise, JsonSerialise};
+
+#[allow(dead_code)]
+struct Parser;
+
+#[allow(dead_code)]
+struct Token;
+
+#[allow(dead_code)]
+type Result<T> = std::result::Result<T, String>;
+
+#[derive(Debug, PartialEq, JsonDeserialise, JsonSerialise)]
+#[json(tag = "type")]
+enum Shape {
+    Circle { radius: u32 },
+    Square { side: Option<u32> },
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise, JsonSerialise)]
+struct Drawing {
+    shapes: Vec<Shape>,
+    #[json(flatten)]
+    origin: Origin,
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise, JsonSerialise)]
+struct Origin {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn test_qualified_paths() {
+    let drawing = Drawing {
+        shapes: vec![Shape::Circle { radius: 2 }, Shape::Square { side: None }],
+        origin: Origin { x: 1, y: -1 },
+    };
+
+    let result = json_parser::Serialiser::serialise(&drawing);
+    assert_eq!(
+        r#"{"shapes":[{"type":"Circle","radius":2},{"type":"Square","side":null}],"x":1,"y":-1}"#,
+        result
+    );
+    assert_eq!(Ok(drawing), json_parser::Parser::parse(&result));
+}
+
+// The crate can be found through a re-export instead
+mod reexport {
+    pub use json_parser as json;
+}
+
+#[derive(Debug, PartialEq, JsonDeserialise, JsonSerialise)]
+#[json(crate = "reexport::json")]
+struct Point(i32, i32);
+
+#[test]
+fn test_crate_path() {
+    let result = json_parser::Serialiser::serialise(&Point(1, 2));
+    assert_eq!("[1,2]", result);
+    assert_eq!(Ok(Point(1, 2)), json_parser::Parser::parse(&result));
+}
@@ -0,0 +1,432 @@
+use std::collections::BTreeMap;
+
+use json_parser::{json_value::JsonValue, *};
+use json_parser_macros::{JsonDeserialise, JsonSerialise};
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+struct Person {
+    name: String,
+    age: u32,
+    nickname: Option<String>,
+    address: Address,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+struct Empty {}
+
+fn person() -> Person {
+    Person {
+        name: "Jane \"JD\" Doe".to_string(),
+        age: 32,
+        nickname: None,
+        address: Address {
+            street: "123 Maple Street".to_string(),
+            city: "Springfield".to_string(),
+        },
+        tags: vec!["user".to_string(), "admin".to_string()],
+    }
+}
+
+#[test]
+fn test_field_order() {
+    let result = Serialiser::serialise(&person());
+    assert_eq!(
+        r#"{"name":"Jane \"JD\" Doe","age":32,"nickname":null,"address":{"street":"123 Maple Street","city":"Springfield"},"tags":["user","admin"]}"#,
+        result
+    );
+}
+
+#[test]
+fn test_empty() {
+    let result = Serialiser::serialise(&Empty {});
+    assert_eq!("{}", result);
+}
+
+#[test]
+fn test_round_trip() {
+    let result = Parser::parse::<Person>(&Serialiser::serialise(&person()));
+    assert_eq!(Ok(person()), result);
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+enum Theme {
+    #[json(rename = "dark")]
+    Dark,
+    Light,
+}
+
+#[test]
+fn test_unit_enum() {
+    assert_eq!(r#""dark""#, Serialiser::serialise(&Theme::Dark));
+    assert_eq!(r#""Light""#, Serialiser::serialise(&Theme::Light));
+
+    let themes = vec![Theme::Dark, Theme::Light];
+    let result = Parser::parse::<Vec<Theme>>(&Serialiser::serialise(&themes));
+    assert_eq!(Ok(themes), result);
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+enum External {
+    Close,
+    Key(String),
+    Move(i32, i32),
+    Click { x: i32, y: i32 },
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+#[json(tag = "type")]
+enum Internal {
+    #[json(rename = "close")]
+    Close,
+    #[json(rename = "click")]
+    Click { x: i32, y: i32 },
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+#[json(tag = "t", content = "c")]
+enum Adjacent {
+    Close,
+    Key(String),
+    Move(i32, i32),
+    Click { x: i32, y: i32 },
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+#[json(untagged)]
+enum Untagged {
+    Close,
+    Move(i32, i32),
+    Click { x: i32, y: i32 },
+    Key(String),
+}
+
+#[test]
+fn test_externally_tagged_enum() {
+    let cases = vec![
+        (External::Close, r#""Close""#),
+        (External::Key("a".to_string()), r#"{"Key":"a"}"#),
+        (External::Move(1, 2), r#"{"Move":[1,2]}"#),
+        (External::Click { x: 1, y: 2 }, r#"{"Click":{"x":1,"y":2}}"#),
+    ];
+
+    for (value, expected) in cases {
+        let result = Serialiser::serialise(&value);
+        assert_eq!(expected, result);
+        assert_eq!(Ok(value), Parser::parse::<External>(&result));
+    }
+}
+
+#[test]
+fn test_internally_tagged_enum() {
+    let cases = vec![
+        (Internal::Close, r#"{"type":"close"}"#),
+        (
+            Internal::Click { x: 1, y: 2 },
+            r#"{"type":"click","x":1,"y":2}"#,
+        ),
+    ];
+
+    for (value, expected) in cases {
+        let result = Serialiser::serialise(&value);
+        assert_eq!(expected, result);
+        assert_eq!(Ok(value), Parser::parse::<Internal>(&result));
+    }
+}
+
+#[test]
+fn test_adjacently_tagged_enum() {
+    let cases = vec![
+        (Adjacent::Close, r#"{"t":"Close"}"#),
+        (Adjacent::Key("a".to_string()), r#"{"t":"Key","c":"a"}"#),
+        (Adjacent::Move(1, 2), r#"{"t":"Move","c":[1,2]}"#),
+        (
+            Adjacent::Click { x: 1, y: 2 },
+            r#"{"t":"Click","c":{"x":1,"y":2}}"#,
+        ),
+    ];
+
+    for (value, expected) in cases {
+        let result = Serialiser::serialise(&value);
+        assert_eq!(expected, result);
+        assert_eq!(Ok(value), Parser::parse::<Adjacent>(&result));
+    }
+}
+
+#[test]
+fn test_untagged_enum() {
+    let cases = vec![
+        (Untagged::Close, "null"),
+        (Untagged::Move(1, 2), "[1,2]"),
+        (Untagged::Click { x: 1, y: 2 }, r#"{"x":1,"y":2}"#),
+        (Untagged::Key("a".to_string()), r#""a""#),
+    ];
+
+    for (value, expected) in cases {
+        let result = Serialiser::serialise(&value);
+        assert_eq!(expected, result);
+        assert_eq!(Ok(value), Parser::parse::<Untagged>(&result));
+    }
+}
+
+// Fields with the same names as variables in the generated code
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+enum Clashing {
+    Named { first: String, serialiser: u32 },
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+#[json(tag = "type")]
+enum ClashingInternal {
+    Named { first: String, serialiser: u32 },
+}
+
+#[test]
+fn test_enum_field_names() {
+    let value = Clashing::Named {
+        first: "a".to_string(),
+        serialiser: 2,
+    };
+    let result = Serialiser::serialise(&value);
+    assert_eq!(r#"{"Named":{"first":"a","serialiser":2}}"#, result);
+    assert_eq!(Ok(value), Parser::parse::<Clashing>(&result));
+
+    let value = ClashingInternal::Named {
+        first: "a".to_string(),
+        serialiser: 2,
+    };
+    let result = Serialiser::serialise(&value);
+    assert_eq!(r#"{"type":"Named","first":"a","serialiser":2}"#, result);
+    assert_eq!(Ok(value), Parser::parse::<ClashingInternal>(&result));
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+#[json(rename_all = "camelCase")]
+struct Account {
+    is_verified: bool,
+    #[json(rename = "ID")]
+    id: u32,
+    status: Status,
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+#[json(rename_all = "SCREAMING-KEBAB-CASE")]
+enum Status {
+    NotStarted,
+    #[json(rename = "done")]
+    Done,
+}
+
+#[test]
+fn test_rename() {
+    let account = Account {
+        is_verified: true,
+        id: 4,
+        status: Status::NotStarted,
+    };
+    let result = Serialiser::serialise(&account);
+    assert_eq!(
+        r#"{"isVerified":true,"ID":4,"status":"NOT-STARTED"}"#,
+        result
+    );
+    assert_eq!(Ok(account), Parser::parse(&result));
+
+    assert_eq!(r#""done""#, Serialiser::serialise(&Status::Done));
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+struct Keywords {
+    r#type: String,
+    r#loop: Loop,
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+#[json(rename_all = "lowercase")]
+enum Loop {
+    r#While,
+    r#For { r#in: u32 },
+}
+
+#[test]
+fn test_raw_identifiers() {
+    // Named without the `r#`, including when renamed
+    let value = Keywords {
+        r#type: "a".to_string(),
+        r#loop: Loop::For { r#in: 3 },
+    };
+    let result = Serialiser::serialise(&value);
+    assert_eq!(r#"{"type":"a","loop":{"for":{"in":3}}}"#, result);
+    assert_eq!(Ok(value), Parser::parse(&result));
+
+    assert_eq!(r#""while""#, Serialiser::serialise(&Loop::While));
+    assert_eq!(Ok(Loop::While), Parser::parse(r#""while""#));
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+struct Forwarded {
+    id: u32,
+    #[json(extra)]
+    rest: BTreeMap<String, JsonValue>,
+}
+
+#[derive(Debug, PartialEq, JsonSerialise)]
+struct OnlyExtra {
+    #[json(extra)]
+    rest: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, PartialEq, JsonSerialise)]
+#[json(tag = "type")]
+enum TaggedExtra {
+    Event {
+        #[json(extra)]
+        rest: BTreeMap<String, u32>,
+    },
+}
+
+#[test]
+fn test_extra_fields() {
+    let value = Forwarded {
+        id: 1,
+        rest: BTreeMap::from([
+            ("a".to_string(), JsonValue::Bool(true)),
+            ("b".to_string(), JsonValue::Null),
+        ]),
+    };
+    assert_eq!(
+        r#"{"id":1,"a":true,"b":null}"#,
+        Serialiser::serialise(&value)
+    );
+
+    let value = OnlyExtra {
+        rest: BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]),
+    };
+    assert_eq!(r#"{"a":1,"b":2}"#, Serialiser::serialise(&value));
+
+    let value = OnlyExtra {
+        rest: BTreeMap::new(),
+    };
+    assert_eq!("{}", Serialiser::serialise(&value));
+
+    let value = TaggedExtra::Event {
+        rest: BTreeMap::from([("a".to_string(), 1)]),
+    };
+    assert_eq!(r#"{"type":"Event","a":1}"#, Serialiser::serialise(&value));
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+#[json(rename_all = "camelCase")]
+struct Metadata {
+    id: u32,
+    created_at: String,
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+struct Document {
+    #[json(flatten)]
+    empty: Empty,
+    #[json(flatten)]
+    metadata: Metadata,
+    title: String,
+}
+
+#[derive(Debug, PartialEq, JsonSerialise)]
+#[json(tag = "kind")]
+enum Resource {
+    Document {
+        #[json(flatten)]
+        metadata: Metadata,
+    },
+}
+
+#[test]
+fn test_flatten() {
+    let metadata = Metadata {
+        id: 1,
+        created_at: "today".to_string(),
+    };
+    let document = Document {
+        empty: Empty {},
+        metadata,
+        title: "Notes".to_string(),
+    };
+
+    let result = Serialiser::serialise(&document);
+    assert_eq!(r#"{"id":1,"createdAt":"today","title":"Notes"}"#, result);
+    assert_eq!(Ok(document), Parser::parse(&result));
+
+    let value = Resource::Document {
+        metadata: Metadata {
+            id: 2,
+            created_at: "now".to_string(),
+        },
+    };
+    assert_eq!(
+        r#"{"kind":"Document","id":2,"createdAt":"now"}"#,
+        Serialiser::serialise(&value)
+    );
+}
+
+#[derive(Debug, PartialEq, JsonSerialise)]
+struct Labelled<'a, T> {
+    label: &'a str,
+    value: T,
+}
+
+#[derive(Debug, PartialEq, JsonSerialise)]
+#[json(untagged)]
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+#[test]
+fn test_generics() {
+    let value = Labelled {
+        label: "size",
+        value: Either::<u32, String>::Left(3),
+    };
+    assert_eq!(
+        r#"{"label":"size","value":3}"#,
+        Serialiser::serialise(&value)
+    );
+
+    let value = Labelled {
+        label: "name",
+        value: Either::<u32, String>::Right("x".to_string()),
+    };
+    assert_eq!(
+        r#"{"label":"name","value":"x"}"#,
+        Serialiser::serialise(&value)
+    );
+}
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+struct UserId(u64);
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+struct Range(u32, u32);
+
+#[derive(Debug, PartialEq, JsonSerialise, JsonDeserialise)]
+struct Nothing;
+
+#[test]
+fn test_tuple_structs() {
+    let result = Serialiser::serialise(&UserId(7));
+    assert_eq!("7", result);
+    assert_eq!(Ok(UserId(7)), Parser::parse(&result));
+
+    let result = Serialiser::serialise(&Range(1, 5));
+    assert_eq!("[1,5]", result);
+    assert_eq!(Ok(Range(1, 5)), Parser::parse(&result));
+
+    let result = Serialiser::serialise(&Nothing);
+    assert_eq!("null", result);
+    assert_eq!(Ok(Nothing), Parser::parse(&result));
+}
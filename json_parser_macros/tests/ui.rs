@@ -0,0 +1,6 @@
+// Compile errors reported by the derives. Run with `TRYBUILD=overwrite` to update the expected output
+#[test]
+fn test_ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
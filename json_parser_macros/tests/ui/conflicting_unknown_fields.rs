@@ -0,0 +1,9 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+#[json(deny_unknown_fields, ignore_unknown_fields)]
+struct Account {
+    id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+#[json(content = "c")]
+enum Event {
+    Click { x: i32 },
+}
+
+fn main() {}
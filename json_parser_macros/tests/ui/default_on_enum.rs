@@ -0,0 +1,10 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+#[json(default)]
+enum Theme {
+    Light,
+    Dark,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(Default, JsonDeserialise)]
+#[json(default)]
+struct Point(i32, i32);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use std::collections::HashMap;
+
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+struct Forwarded {
+    #[json(extra, flatten)]
+    rest: HashMap<String, u32>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use std::collections::HashMap;
+
+use json_parser_macros::JsonSerialise;
+
+#[derive(JsonSerialise)]
+enum Message {
+    Text(String),
+    Custom(#[json(extra)] HashMap<String, String>),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+struct Metadata {
+    id: u32,
+}
+
+#[derive(JsonDeserialise)]
+struct Document(String, #[json(flatten)] Metadata);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use json_parser_macros::JsonSerialise;
+
+#[derive(JsonSerialise)]
+#[json(tag = "type")]
+enum Event {
+    Click { x: i32 },
+    Key(u32),
+}
+
+fn main() {}
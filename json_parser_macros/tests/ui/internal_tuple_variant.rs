@@ -0,0 +1,10 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+#[json(tag = "type")]
+enum Event {
+    Click { x: i32 },
+    Move(i32, i32),
+}
+
+fn main() {}
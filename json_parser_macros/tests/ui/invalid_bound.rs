@@ -0,0 +1,9 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+#[json(bound = "T Parse")]
+struct Batch<T> {
+    values: Vec<T>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+struct Server {
+    #[json(default = "8080")]
+    port: u16,
+}
+
+fn main() {}
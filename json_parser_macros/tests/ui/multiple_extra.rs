@@ -0,0 +1,13 @@
+use std::collections::HashMap;
+
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+struct Forwarded {
+    #[json(extra)]
+    rest: HashMap<String, u32>,
+    #[json(extra)]
+    more: HashMap<String, u32>,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+#[json(rename_all = "camelCase")]
+struct UserId(u32);
+
+fn main() {}
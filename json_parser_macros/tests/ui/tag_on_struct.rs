@@ -0,0 +1,10 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+#[json(tag = "type")]
+struct Click {
+    x: i32,
+    y: i32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+union Number {
+    int: i32,
+    float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+#[json(rename_fields = "camelCase")]
+struct Account {
+    id: u32,
+}
+
+fn main() {}
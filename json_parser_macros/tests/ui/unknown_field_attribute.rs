@@ -0,0 +1,9 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+struct Account {
+    #[json(skip)]
+    id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use json_parser_macros::JsonSerialise;
+
+#[derive(JsonSerialise)]
+enum Theme {
+    #[json(alias = "light")]
+    Light,
+}
+
+fn main() {}
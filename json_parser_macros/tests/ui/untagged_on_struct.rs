@@ -0,0 +1,10 @@
+use json_parser_macros::JsonSerialise;
+
+#[derive(JsonSerialise)]
+#[json(untagged)]
+struct Click {
+    x: i32,
+    y: i32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use json_parser_macros::JsonDeserialise;
+
+#[derive(JsonDeserialise)]
+#[json(tag = "type", untagged)]
+enum Event {
+    Click { x: i32 },
+}
+
+fn main() {}
@@ -1,7 +1,6 @@
 use json_parser::*;
-use json_parser_macros::JsonDeserialise;
 
-#[derive(Debug, JsonDeserialise)]
+#[derive(Debug, JsonDeserialise, JsonSerialise)]
 pub struct Root {
     pub name: String,
     pub age: u32,
@@ -16,14 +15,14 @@ pub struct Root {
     pub numbers: Numbers,
 }
 
-#[derive(Debug, JsonDeserialise)]
+#[derive(Debug, JsonDeserialise, JsonSerialise)]
 pub struct Contact {
     pub email: String,
     pub phone: String,
     pub address: Address,
 }
 
-#[derive(Debug, JsonDeserialise)]
+#[derive(Debug, JsonDeserialise, JsonSerialise)]
 pub struct Address {
     pub street: String,
     pub city: String,
@@ -31,27 +30,34 @@ pub struct Address {
     pub country: String,
 }
 
-#[derive(Debug, JsonDeserialise)]
+#[derive(Debug, JsonDeserialise, JsonSerialise)]
 pub struct Preferences {
     pub notifications: Notifications,
-    pub theme: String,
+    pub theme: Theme,
     pub language: String,
 }
 
-#[derive(Debug, JsonDeserialise)]
+#[derive(Debug, JsonDeserialise, JsonSerialise)]
+#[json(rename_all = "lowercase")]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+#[derive(Debug, JsonDeserialise, JsonSerialise)]
 pub struct Notifications {
     pub email: bool,
     pub sms: bool,
 }
 
-#[derive(Debug, JsonDeserialise)]
+#[derive(Debug, JsonDeserialise, JsonSerialise)]
 pub struct History {
     pub login: String,
     pub ip: String,
     pub success: bool,
 }
 
-#[derive(Debug, JsonDeserialise)]
+#[derive(Debug, JsonDeserialise, JsonSerialise)]
 pub struct Numbers {
     pub int: i64,
     pub float: f64,
@@ -64,4 +70,8 @@ pub struct Numbers {
 fn main() {
     let result = Parser::parse::<Root>(include_str!("test_data/test_blob.json"));
     println!("{result:#?}");
+
+    if let Ok(root) = result {
+        println!("{}", Serialiser::serialise(&root));
+    }
 }